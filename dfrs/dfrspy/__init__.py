@@ -1,3 +1,3 @@
-from dfrs import DataFrame, Series
+from dfrs import DataFrame, Series, SeriesBuilder
 
-__all__ = ["DataFrame", "Series"]
+__all__ = ["DataFrame", "Series", "SeriesBuilder"]
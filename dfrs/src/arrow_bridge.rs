@@ -0,0 +1,160 @@
+// Conversions between Series and Arrow arrays. Primitive values move into an Arrow buffer
+// without re-encoding, but still cost one copy since Arrow cannot adopt a Series buffer.
+use crate::{ConcreteBool, ConcreteFloat, ConcreteInt, ConcreteString, Series};
+use arrow::array::{Array, ArrayRef, BooleanArray, Float64Array, Int64Array, StringArray};
+use arrow::datatypes::DataType;
+use pyo3::exceptions::PyValueError;
+use pyo3::PyResult;
+use std::sync::Arc;
+
+pub(crate) fn to_arrow(series: &Series) -> ArrayRef {
+    if series.validity().is_some() {
+        let valid = |i: usize| series.is_valid(i);
+        return match series {
+            Series::Int(ConcreteInt { items }) => Arc::new(Int64Array::from_iter(
+                items
+                    .iter()
+                    .enumerate()
+                    .map(|(i, x)| valid(i).then_some(*x)),
+            )),
+            Series::Float(ConcreteFloat { items }) => Arc::new(Float64Array::from_iter(
+                items
+                    .iter()
+                    .enumerate()
+                    .map(|(i, x)| valid(i).then_some(*x)),
+            )),
+            Series::String(ConcreteString { items }) => Arc::new(StringArray::from_iter(
+                items.iter().enumerate().map(|(i, x)| valid(i).then_some(x)),
+            )),
+            Series::Bool(ConcreteBool { items }) => Arc::new(BooleanArray::from_iter(
+                items
+                    .iter()
+                    .enumerate()
+                    .map(|(i, x)| valid(i).then_some(*x)),
+            )),
+        };
+    }
+    match series {
+        Series::Int(ConcreteInt { items }) => Arc::new(Int64Array::from(items.to_vec())),
+        Series::Float(ConcreteFloat { items }) => Arc::new(Float64Array::from(items.to_vec())),
+        Series::String(ConcreteString { items }) => {
+            Arc::new(StringArray::from_iter_values(items.iter()))
+        }
+        Series::Bool(ConcreteBool { items }) => Arc::new(BooleanArray::from(items.to_vec())),
+    }
+}
+
+/// Arrow nulls become null slots, holding the usual placeholders rather than whatever Arrow
+/// left in their place.
+pub(crate) fn from_arrow(array: &dyn Array) -> PyResult<Series> {
+    let any = array.as_any();
+    let series = match array.data_type() {
+        DataType::Int64 => Ok(Series::Int(ConcreteInt {
+            items: any
+                .downcast_ref::<Int64Array>()
+                .unwrap()
+                .values()
+                .to_vec()
+                .into(),
+        })),
+        DataType::Float64 => Ok(Series::Float(ConcreteFloat {
+            items: any
+                .downcast_ref::<Float64Array>()
+                .unwrap()
+                .values()
+                .to_vec()
+                .into(),
+        })),
+        DataType::Utf8 => Ok(Series::String(ConcreteString {
+            items: any
+                .downcast_ref::<StringArray>()
+                .unwrap()
+                .iter()
+                .map(|s| s.unwrap_or_default().to_owned())
+                .collect(),
+        })),
+        DataType::Boolean => Ok(Series::Bool(ConcreteBool {
+            items: any
+                .downcast_ref::<BooleanArray>()
+                .unwrap()
+                .values()
+                .iter()
+                .collect(),
+        })),
+        other => Err(PyValueError::new_err(format!(
+            "Unsupported Arrow type {other}"
+        ))),
+    }?;
+    if array.null_count() == 0 {
+        return Ok(series);
+    }
+    let positions: Vec<Option<usize>> = (0..array.len())
+        .map(|i| array.is_valid(i).then_some(i))
+        .collect();
+    Ok(series.take_or_null(&positions))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip() {
+        let series = [
+            Series::Int(ConcreteInt {
+                items: vec![1, -2, 3].into(),
+            }),
+            Series::Float(ConcreteFloat {
+                items: vec![0.1, f64::INFINITY, -2.5].into(),
+            }),
+            Series::String(ConcreteString {
+                items: vec!["a".to_owned(), "".to_owned(), "ü".to_owned()].into(),
+            }),
+            Series::Bool(ConcreteBool {
+                items: vec![true, false, true].into(),
+            }),
+        ];
+        for s in series {
+            let array = to_arrow(&s);
+            assert_eq!(array.len(), 3);
+            assert_eq!(from_arrow(array.as_ref()).unwrap().__repr__(), s.__repr__());
+        }
+    }
+
+    #[test]
+    fn test_exports_nulls() {
+        let series = Series::Int(ConcreteInt {
+            items: vec![1, 0, 3].into(),
+        })
+        .take_or_null(&[Some(0), None, Some(2)]);
+        let array = to_arrow(&series);
+        assert_eq!(array.null_count(), 1);
+        assert!(array.is_null(1));
+    }
+
+    #[test]
+    fn test_imports_nulls() {
+        let array = Int64Array::from(vec![Some(1), None]);
+        assert_eq!(
+            from_arrow(&array).unwrap().__repr__(),
+            "IntSeries([1, None])"
+        );
+        let series = [
+            Series::Float(ConcreteFloat {
+                items: vec![0.5, 2.0].into(),
+            }),
+            Series::String(ConcreteString {
+                items: vec!["a".to_owned(), "b".to_owned()].into(),
+            }),
+            Series::Bool(ConcreteBool {
+                items: vec![true, true].into(),
+            }),
+        ];
+        for s in series {
+            let s = s.take_or_null(&[None, Some(1), Some(0)]);
+            let round_trip = from_arrow(to_arrow(&s).as_ref()).unwrap();
+            assert_eq!(round_trip.__repr__(), s.__repr__());
+            assert_eq!(round_trip.validity(), s.validity());
+        }
+    }
+}
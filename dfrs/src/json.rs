@@ -0,0 +1,277 @@
+// Just enough JSON for `DataFrame.from_json`, parsed by hand so the crate needn't depend on
+// serde. Errors report the byte offset where parsing stopped.
+use std::error::Error;
+
+/// Arrays and objects nested deeper than this are rejected rather than risking the stack.
+const MAX_DEPTH: usize = 128;
+
+#[derive(Debug, PartialEq)]
+pub(crate) enum Value {
+    Null,
+    Bool(bool),
+    Int(i64),
+    /// Numbers with a fraction or exponent, or too large for an i64.
+    Float(f64),
+    String(String),
+    Array(Vec<Value>),
+    /// Members in document order.
+    Object(Vec<(String, Value)>),
+}
+
+pub(crate) fn parse(text: &str) -> Result<Value, Box<dyn Error>> {
+    let mut parser = Parser {
+        text,
+        position: 0,
+        depth: 0,
+    };
+    let value = parser.value()?;
+    parser.skip_whitespace();
+    if parser.position < text.len() {
+        return Err(parser.error("unexpected trailing characters"));
+    }
+    Ok(value)
+}
+
+struct Parser<'t> {
+    text: &'t str,
+    position: usize,
+    depth: usize,
+}
+
+impl Parser<'_> {
+    fn error(&self, message: &str) -> Box<dyn Error> {
+        format!("{message} at byte {}", self.position).into()
+    }
+
+    fn skip_whitespace(&mut self) {
+        let rest = &self.text[self.position..];
+        self.position += rest.len() - rest.trim_start_matches([' ', '\t', '\n', '\r']).len();
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.text[self.position..].chars().next()
+    }
+
+    /// Consumes `c` if it is next.
+    fn eat(&mut self, c: char) -> bool {
+        let found = self.peek() == Some(c);
+        if found {
+            self.position += c.len_utf8();
+        }
+        found
+    }
+
+    fn expect(&mut self, c: char) -> Result<(), Box<dyn Error>> {
+        self.skip_whitespace();
+        if self.eat(c) {
+            Ok(())
+        } else {
+            Err(self.error(&format!("expected {c:?}")))
+        }
+    }
+
+    fn value(&mut self) -> Result<Value, Box<dyn Error>> {
+        self.skip_whitespace();
+        match self.peek() {
+            Some('[') => self.nested(Self::array),
+            Some('{') => self.nested(Self::object),
+            Some('"') => Ok(Value::String(self.string()?)),
+            Some('t') => self.literal("true", Value::Bool(true)),
+            Some('f') => self.literal("false", Value::Bool(false)),
+            Some('n') => self.literal("null", Value::Null),
+            Some('-' | '0'..='9') => self.number(),
+            Some(c) => Err(self.error(&format!("unexpected {c:?}"))),
+            None => Err(self.error("unexpected end of input")),
+        }
+    }
+
+    fn nested(
+        &mut self,
+        parse: fn(&mut Self) -> Result<Value, Box<dyn Error>>,
+    ) -> Result<Value, Box<dyn Error>> {
+        if self.depth == MAX_DEPTH {
+            return Err(self.error("nesting too deep"));
+        }
+        self.depth += 1;
+        let value = parse(self);
+        self.depth -= 1;
+        value
+    }
+
+    fn literal(&mut self, word: &str, value: Value) -> Result<Value, Box<dyn Error>> {
+        if self.text[self.position..].starts_with(word) {
+            self.position += word.len();
+            Ok(value)
+        } else {
+            Err(self.error("invalid literal"))
+        }
+    }
+
+    fn number(&mut self) -> Result<Value, Box<dyn Error>> {
+        let rest = &self.text[self.position..];
+        let len = rest
+            .find(|c: char| !(c.is_ascii_digit() || "+-.eE".contains(c)))
+            .unwrap_or(rest.len());
+        let token = &rest[..len];
+        if !token.contains(['.', 'e', 'E']) {
+            if let Ok(i) = token.parse() {
+                self.position += len;
+                return Ok(Value::Int(i));
+            }
+        }
+        let x = token
+            .parse()
+            .map_err(|_| self.error(&format!("invalid number {token:?}")))?;
+        self.position += len;
+        Ok(Value::Float(x))
+    }
+
+    fn string(&mut self) -> Result<String, Box<dyn Error>> {
+        self.expect('"')?;
+        let mut out = String::new();
+        loop {
+            let c = self
+                .peek()
+                .ok_or_else(|| self.error("unterminated string"))?;
+            self.position += c.len_utf8();
+            match c {
+                '"' => return Ok(out),
+                '\\' => {
+                    let escaped = self
+                        .peek()
+                        .ok_or_else(|| self.error("unterminated string"))?;
+                    self.position += escaped.len_utf8();
+                    out.push(match escaped {
+                        '"' | '\\' | '/' => escaped,
+                        'b' => '\u{8}',
+                        'f' => '\u{c}',
+                        'n' => '\n',
+                        'r' => '\r',
+                        't' => '\t',
+                        'u' => self.unicode_escape()?,
+                        _ => return Err(self.error(&format!("invalid escape {escaped:?}"))),
+                    });
+                }
+                c => out.push(c),
+            }
+        }
+    }
+
+    /// The character of a `\uXXXX` escape whose `\u` has been consumed, combining a UTF-16
+    /// surrogate pair.
+    fn unicode_escape(&mut self) -> Result<char, Box<dyn Error>> {
+        let mut code = self.hex4()?;
+        if (0xD800..0xDC00).contains(&code) {
+            if !self.text[self.position..].starts_with("\\u") {
+                return Err(self.error("unpaired surrogate"));
+            }
+            self.position += 2;
+            let low = self.hex4()?;
+            if !(0xDC00..0xE000).contains(&low) {
+                return Err(self.error("unpaired surrogate"));
+            }
+            code = 0x10000 + ((code - 0xD800) << 10) + (low - 0xDC00);
+        }
+        char::from_u32(code).ok_or_else(|| self.error("invalid unicode escape"))
+    }
+
+    fn hex4(&mut self) -> Result<u32, Box<dyn Error>> {
+        let code = self
+            .text
+            .get(self.position..self.position + 4)
+            .filter(|digits| digits.chars().all(|c| c.is_ascii_hexdigit()))
+            .and_then(|digits| u32::from_str_radix(digits, 16).ok())
+            .ok_or_else(|| self.error("invalid unicode escape"))?;
+        self.position += 4;
+        Ok(code)
+    }
+
+    fn array(&mut self) -> Result<Value, Box<dyn Error>> {
+        self.expect('[')?;
+        let mut items = vec![];
+        self.skip_whitespace();
+        if self.eat(']') {
+            return Ok(Value::Array(items));
+        }
+        loop {
+            items.push(self.value()?);
+            self.skip_whitespace();
+            if self.eat(']') {
+                return Ok(Value::Array(items));
+            }
+            if !self.eat(',') {
+                return Err(self.error("expected ',' or ']'"));
+            }
+        }
+    }
+
+    fn object(&mut self) -> Result<Value, Box<dyn Error>> {
+        self.expect('{')?;
+        let mut members = vec![];
+        self.skip_whitespace();
+        if self.eat('}') {
+            return Ok(Value::Object(members));
+        }
+        loop {
+            let key = self.string()?;
+            self.expect(':')?;
+            members.push((key, self.value()?));
+            self.skip_whitespace();
+            if self.eat('}') {
+                return Ok(Value::Object(members));
+            }
+            if !self.eat(',') {
+                return Err(self.error("expected ',' or '}'"));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_values() {
+        let text = r#" [{"a": 1, "b": -2.5e1, "c": "x\"ü😀", "d": [true, null]}, {}] "#;
+        assert_eq!(
+            parse(text).unwrap(),
+            Value::Array(vec![
+                Value::Object(vec![
+                    ("a".to_owned(), Value::Int(1)),
+                    ("b".to_owned(), Value::Float(-25.0)),
+                    ("c".to_owned(), Value::String("x\"ü😀".to_owned())),
+                    (
+                        "d".to_owned(),
+                        Value::Array(vec![Value::Bool(true), Value::Null])
+                    ),
+                ]),
+                Value::Object(vec![]),
+            ])
+        );
+        assert_eq!(
+            parse(r#""\u00e9\ud83d\ude00\n""#).unwrap(),
+            Value::String("é😀\n".to_owned())
+        );
+        assert_eq!(parse("99999999999999999999").unwrap(), Value::Float(1e20));
+    }
+
+    #[test]
+    fn test_errors() {
+        for (text, message) in [
+            ("[1, 2", "expected ',' or ']' at byte 5"),
+            ("{\"a\" 1}", "expected ':' at byte 5"),
+            ("[1] x", "unexpected trailing characters at byte 4"),
+            ("\"abc", "unterminated string at byte 4"),
+            ("[tru]", "invalid literal at byte 1"),
+            ("-", "invalid number \"-\" at byte 0"),
+        ] {
+            assert_eq!(parse(text).unwrap_err().to_string(), message, "{text:?}");
+        }
+        let deep = "[".repeat(MAX_DEPTH + 1);
+        assert!(parse(&deep)
+            .unwrap_err()
+            .to_string()
+            .starts_with("nesting too deep"));
+    }
+}
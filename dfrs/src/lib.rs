@@ -1,22 +1,160 @@
+use indexmap::IndexMap;
+use pyo3::basic::CompareOp;
+use pyo3::exceptions::{
+    PyIndexError, PyKeyError, PyOverflowError, PyTypeError, PyValueError, PyZeroDivisionError,
+};
 use pyo3::prelude::*;
-use pyo3::{exceptions::PyKeyError, exceptions::PyValueError, types::PyDict, types::PyString};
-use std::collections::HashSet;
+use pyo3::types::{PyBool, PyDict, PyFloat, PyList, PySlice, PySliceIndices, PyString, PyTuple};
+use std::collections::{BTreeMap, HashSet};
 use std::error::Error;
 use std::fs::File;
-use std::io::{BufRead, BufReader, Read};
-use std::{collections::HashMap, ops::Add};
+use std::hash::Hash;
+use std::io::{BufRead, BufReader, BufWriter, Read, Write};
+use std::sync::{Arc, OnceLock, RwLock};
+use std::{
+    cmp::Ordering,
+    collections::HashMap,
+    ops::{Add, Deref, DerefMut, Sub},
+};
+
+#[cfg(feature = "arrow")]
+mod arrow_bridge;
+mod json;
 
 trait ConcreteArrayTrait: std::fmt::Debug + Add + Sized {
     fn len(&self) -> usize;
 }
 
+/// Reference-counted element storage: clones share the allocation (so a column can also back
+/// an executor `Var` without copying), and mutation copies only while it is shared. Nulls are
+/// tracked in an optional validity bitmap, `None` when every element is valid; a null slot
+/// still holds a placeholder value in `items`.
+#[derive(Default, PartialEq)]
+struct Buffer<T> {
+    items: Arc<Vec<T>>,
+    validity: Option<Arc<exec::BitVec>>,
+}
+
+impl<T> Buffer<T> {
+    fn is_valid(&self, i: usize) -> bool {
+        self.validity
+            .as_ref()
+            .is_none_or(|v| v.get(i) == Some(true))
+    }
+
+    /// Replaces the validity bitmap, dropping it when nothing is null.
+    fn with_validity(mut self, validity: Option<Arc<exec::BitVec>>) -> Self {
+        self.validity = validity.filter(|v| v.iter().any(|valid| !valid));
+        self
+    }
+
+    /// Appends a valid `item`.
+    fn push(&mut self, item: T)
+    where
+        T: Clone,
+    {
+        if let Some(validity) = &mut self.validity {
+            *validity = Arc::new(validity.iter().chain([true]).collect());
+        }
+        Arc::make_mut(&mut self.items).push(item);
+    }
+}
+
+/// Renders a null slot as Python does.
+struct Null;
+
+impl std::fmt::Debug for Null {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("None")
+    }
+}
+
+impl<T: std::fmt::Debug> std::fmt::Debug for Buffer<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.validity.is_none() {
+            return self.items.fmt(f);
+        }
+        f.debug_list()
+            .entries(self.items.iter().enumerate().map(|(i, x)| {
+                if self.is_valid(i) {
+                    x as &dyn std::fmt::Debug
+                } else {
+                    &Null
+                }
+            }))
+            .finish()
+    }
+}
+
+impl<T> Clone for Buffer<T> {
+    fn clone(&self) -> Self {
+        Self {
+            items: self.items.clone(),
+            validity: self.validity.clone(),
+        }
+    }
+}
+
+impl<T> Deref for Buffer<T> {
+    type Target = Vec<T>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.items
+    }
+}
+
+impl<T: Clone> DerefMut for Buffer<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        Arc::make_mut(&mut self.items)
+    }
+}
+
+impl<T> From<Vec<T>> for Buffer<T> {
+    fn from(items: Vec<T>) -> Self {
+        Self {
+            items: Arc::new(items),
+            validity: None,
+        }
+    }
+}
+
+impl<T> FromIterator<T> for Buffer<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        Self::from(iter.into_iter().collect::<Vec<_>>())
+    }
+}
+
+impl<T: Clone> IntoIterator for Buffer<T> {
+    type Item = T;
+    type IntoIter = std::vec::IntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        Arc::unwrap_or_clone(self.items).into_iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a Buffer<T> {
+    type Item = &'a T;
+    type IntoIter = std::slice::Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.items.iter()
+    }
+}
+
 // We cannot have a generic pyclass for obvious reasons so we template out the variants explicitly.
 macro_rules! create_concrete_array {
     ($name: tt, $type: ty) => {
         #[pyclass]
         #[derive(Clone, Debug)]
         struct $name {
-            items: Vec<$type>,
+            items: Buffer<$type>,
+        }
+
+        impl $name {
+            fn push(&mut self, item: $type) {
+                self.items.push(item);
+            }
         }
     };
 }
@@ -24,26 +162,50 @@ macro_rules! create_concrete_array {
 create_concrete_array!(ConcreteString, String);
 create_concrete_array!(ConcreteFloat, f64);
 create_concrete_array!(ConcreteInt, i64);
+create_concrete_array!(ConcreteBool, bool);
+
+impl ConcreteString {
+    /// Joins corresponding elements with `sep` between them.
+    fn cat(&self, rhs: &Self, sep: &str) -> Self {
+        Self {
+            items: self
+                .items
+                .iter()
+                .zip(rhs.items.iter())
+                .map(|(x, y)| format!("{x}{sep}{y}"))
+                .collect(),
+        }
+    }
+}
 
 impl Add for ConcreteString {
     type Output = Self;
 
+    fn add(self, rhs: Self) -> Self::Output {
+        self.cat(&rhs, "")
+    }
+}
+
+impl Add for ConcreteBool {
+    type Output = Self;
+
     fn add(self, _: Self) -> Self::Output {
         panic!("Programmer error to ever arrive here.")
     }
 }
 
 impl Add for ConcreteInt {
-    type Output = Self;
+    type Output = PyResult<Self>;
 
     fn add(self, rhs: Self) -> Self::Output {
-        let items: Vec<i64> = self
+        let items = self
             .items
-            .into_iter()
-            .zip(rhs.items.into_iter())
-            .map(|(x, y)| x + y)
-            .collect();
-        Self { items }
+            .iter()
+            .zip(&rhs.items)
+            .enumerate()
+            .map(|(i, (x, y))| checked_add(i, *x, *y))
+            .collect::<PyResult<_>>()?;
+        Ok(Self { items })
     }
 }
 
@@ -57,10 +219,53 @@ impl Add for ConcreteFloat {
             .zip(rhs.items.into_iter())
             .map(|(x, y)| x + y)
             .collect();
+        Self {
+            items: items.into(),
+        }
+    }
+}
+
+impl Sub for ConcreteInt {
+    type Output = PyResult<Self>;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        let items = self
+            .items
+            .iter()
+            .zip(&rhs.items)
+            .enumerate()
+            .map(|(i, (x, y))| checked_sub(i, *x, *y))
+            .collect::<PyResult<_>>()?;
+        Ok(Self { items })
+    }
+}
+
+impl Sub for ConcreteFloat {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        let items = self
+            .items
+            .iter()
+            .zip(&rhs.items)
+            .map(|(x, y)| x - y)
+            .collect();
         Self { items }
     }
 }
 
+/// `x + y`, raising an overflow error naming index `i` instead of wrapping.
+fn checked_add(i: usize, x: i64, y: i64) -> PyResult<i64> {
+    x.checked_add(y)
+        .ok_or_else(|| PyOverflowError::new_err(format!("{x} + {y} overflows Int at index {i}")))
+}
+
+/// `x - y`, raising an overflow error naming index `i` instead of wrapping.
+fn checked_sub(i: usize, x: i64, y: i64) -> PyResult<i64> {
+    x.checked_sub(y)
+        .ok_or_else(|| PyOverflowError::new_err(format!("{x} - {y} overflows Int at index {i}")))
+}
+
 impl ConcreteArrayTrait for ConcreteInt {
     fn len(&self) -> usize {
         self.items.len()
@@ -76,18 +281,23 @@ impl ConcreteArrayTrait for ConcreteFloat {
         self.items.len()
     }
 }
+impl ConcreteArrayTrait for ConcreteBool {
+    fn len(&self) -> usize {
+        self.items.len()
+    }
+}
 
 // We could use trait objects if we didn't know all the types ahead of time.
 // struct AltSeries {
 //     item: Box<dyn ConcreteArrayTrait>
 // }
-// TODO: shared buffers (e.g. using Arc)?
 #[pyclass]
 #[derive(Clone)]
 enum Series {
     Int(ConcreteInt),
     Float(ConcreteFloat),
     String(ConcreteString),
+    Bool(ConcreteBool),
 }
 
 impl std::fmt::Debug for Series {
@@ -96,6 +306,7 @@ impl std::fmt::Debug for Series {
             Series::Int(ConcreteInt { items }) => write!(f, "IntSeries({items:?})"),
             Series::Float(ConcreteFloat { items }) => write!(f, "FloatSeries({items:?})"),
             Series::String(ConcreteString { items }) => write!(f, "StringSeries({items:?})"),
+            Series::Bool(ConcreteBool { items }) => write!(f, "BoolSeries({items:?})"),
         }
     }
 }
@@ -104,12 +315,23 @@ impl std::fmt::Debug for Series {
 impl Series {
     #[new]
     fn create(pylist: Bound<'_, PyAny>) -> PyResult<Self> {
-        if let Ok(items) = pylist.extract::<Vec<i64>>() {
-            Ok(Series::Int(ConcreteInt { items }))
+        // Python bools are also ints, so they must be tried first.
+        if let Ok(items) = pylist.extract::<Vec<bool>>() {
+            Ok(Series::Bool(ConcreteBool {
+                items: items.into(),
+            }))
+        } else if let Ok(items) = pylist.extract::<Vec<i64>>() {
+            Ok(Series::Int(ConcreteInt {
+                items: items.into(),
+            }))
         } else if let Ok(items) = pylist.extract::<Vec<f64>>() {
-            Ok(Series::Float(ConcreteFloat { items }))
+            Ok(Series::Float(ConcreteFloat {
+                items: items.into(),
+            }))
         } else if let Ok(items) = pylist.extract::<Vec<String>>() {
-            Ok(Series::String(ConcreteString { items }))
+            Ok(Series::String(ConcreteString {
+                items: items.into(),
+            }))
         } else {
             Err(PyValueError::new_err("Invalid item type"))
         }
@@ -120,324 +342,6704 @@ impl Series {
             Series::Int(ConcreteInt { items }) => format!("IntSeries({items:?})"),
             Series::Float(ConcreteFloat { items }) => format!("FloatSeries({items:?})"),
             Series::String(ConcreteString { items }) => format!("StringSeries({items:?})"),
+            Series::Bool(ConcreteBool { items }) => format!("BoolSeries({items:?})"),
         }
     }
 
-    fn __add__(slf: PyRef<'_, Self>, other: Bound<'_, PyAny>) -> PyResult<Self> {
-        let slf = slf.clone();
-        let other: Series = other.extract()?;
-        slf.add(other)
+    fn __len__(&self) -> usize {
+        self.len()
     }
-}
 
-#[derive(PartialEq, Eq, Debug, Clone, Copy, Hash)]
-enum Dtype {
-    Int,
-    Float,
-    String,
-}
-
-impl Dtype {
-    fn infer(s: &str) -> Option<Self> {
-        if s.len() == 1 && s == "\"" {
-            return None;
-        }
-        let mut cs = s.chars();
-        let first = cs.next()?;
-        let last = cs.next_back();
-        if first == '\"' && last.is_some() && last.unwrap() == '\"' {
-            return Some(Self::String);
+    /// Element at an integer `key`, counting from the end when negative, or a new Series of
+    /// the elements a slice `key` selects.
+    fn __getitem__(&self, py: Python<'_>, key: Bound<'_, PyAny>) -> PyResult<PyObject> {
+        if let Ok(slice) = key.downcast::<PySlice>() {
+            let len = self.len().try_into()?;
+            let PySliceIndices {
+                start,
+                step,
+                slicelength,
+                ..
+            } = slice.indices(len)?;
+            let positions: Vec<usize> = (0..slicelength as isize)
+                .map(|k| (start + k * step) as usize)
+                .collect();
+            return Ok(self.take(&positions).into_py(py));
         }
+        let position = resolve_index(key.extract()?, self.len())?;
+        Ok(self.get(py, position))
+    }
 
-        fn acceptable_char(c: char) -> bool {
-            c.is_ascii_digit() || c == '.' || c == '-'
-        }
-        if s.chars().all(acceptable_char)
-            && acceptable_char(first)
-            && last.map_or_else(|| true, acceptable_char)
-        {
-            if s.contains('.') {
-                Some(Self::Float)
-            } else {
-                Some(Self::Int)
-            }
-        } else {
-            None
-        }
+    fn __add__(&self, other: Bound<'_, PyAny>) -> PyResult<Self> {
+        self.arithmetic(other, Self::add_op)
     }
-}
 
-fn collect_early_exit<'s, Input: 's, Return, F, E, It>(it: It, func: F) -> Result<Vec<Return>, E>
-where
-    It: Iterator<Item = &'s Input>,
-    F: Fn(&Input) -> Result<Return, E>,
-{
-    let mut vec = Vec::new();
-    for item in it {
-        vec.push(func(item)?);
+    fn __sub__(&self, other: Bound<'_, PyAny>) -> PyResult<Self> {
+        self.arithmetic(other, Self::sub_op)
     }
-    Ok(vec)
-}
 
-impl Series {
-    fn infer_dtype(sl: &[String]) -> Result<Dtype, Box<dyn Error>> {
-        let (dtype_success, dtype_fail): (HashSet<_>, HashSet<_>) = sl
-            .iter()
-            .map(|s| Dtype::infer(s.as_str()))
-            .partition(|opt| opt.is_some());
-        if dtype_fail.is_empty() {
-            let mut success_iter = dtype_success.into_iter().map(|opt| opt.unwrap());
-            if let Some(mut dtype) = success_iter.next() {
-                for entry in success_iter {
-                    match (entry, dtype) {
-                        (Dtype::Float, Dtype::Int) => {
-                            dtype = Dtype::Float;
-                        }
-                        (Dtype::Int, Dtype::Float) => {
-                            dtype = Dtype::Float;
-                        }
-                        (x, y) if x == y => {
-                            continue;
-                        }
-                        _ => {
-                            return Err(format!(
-                                "Incompatible mixture of dtypes inferred: {entry:?} and {dtype:?}"
-                            )
-                            .into())
-                        }
-                    };
-                }
-                Ok(dtype)
-            } else {
-                Err("Empty sequence of entries provided for inference".into())
-            }
-        } else {
-            Err(format!("Failed to parse some elements: {dtype_fail:?}").into())
-        }
+    fn __mul__(&self, other: Bound<'_, PyAny>) -> PyResult<Self> {
+        self.arithmetic(other, Self::mul_op)
     }
-    fn from_untyped(sl: &[String]) -> Result<Self, Box<dyn Error>> {
-        let target_dtype = Self::infer_dtype(sl)?;
-        match target_dtype {
-            Dtype::Int => Ok(Series::Int(ConcreteInt {
-                items: collect_early_exit(sl.iter(), |item| item.parse::<i64>())?,
-            })),
-            Dtype::Float => Ok(Series::Float(ConcreteFloat {
-                items: collect_early_exit(sl.iter(), |item| item.parse::<f64>())?,
-            })),
-            Dtype::String => Ok(Series::String(ConcreteString {
-                items: sl
-                    .iter()
-                    .map(|s| s.trim_matches('\"'))
-                    .map(|s| s.to_owned())
-                    .collect(),
-            })),
-        }
+
+    fn __truediv__(&self, other: Bound<'_, PyAny>) -> PyResult<Self> {
+        self.arithmetic(other, Self::div_op)
     }
-    fn dtype(&self) -> Dtype {
-        match self {
-            Self::Int(_) => Dtype::Int,
-            Self::Float(_) => Dtype::Float,
-            Self::String(_) => Dtype::String,
-        }
+
+    fn __lt__(&self, other: Bound<'_, PyAny>) -> PyResult<Self> {
+        self.compare_op(Operand::extract(&other)?, CompareOp::Lt)
     }
 
-    fn i64(self) -> Result<ConcreteInt, Box<dyn Error>> {
-        match self {
-            Self::Int(concrete) => Ok(concrete),
-            _ => Err("Not Int Series".into()),
-        }
+    fn __le__(&self, other: Bound<'_, PyAny>) -> PyResult<Self> {
+        self.compare_op(Operand::extract(&other)?, CompareOp::Le)
     }
 
-    fn f64(self) -> Result<ConcreteFloat, Box<dyn Error>> {
-        match self {
-            Self::Float(concrete) => Ok(concrete),
-            _ => Err("Not Float Series".into()),
-        }
+    fn __gt__(&self, other: Bound<'_, PyAny>) -> PyResult<Self> {
+        self.compare_op(Operand::extract(&other)?, CompareOp::Gt)
     }
 
-    fn string(self) -> Result<ConcreteString, Box<dyn Error>> {
-        match self {
-            Self::String(concrete) => Ok(concrete),
-            _ => Err("Not String Series".into()),
-        }
+    fn __ge__(&self, other: Bound<'_, PyAny>) -> PyResult<Self> {
+        self.compare_op(Operand::extract(&other)?, CompareOp::Ge)
     }
 
-    fn promote(lhs: Self, rhs: Self) -> PyResult<(Self, Self, Dtype)> {
-        match (&lhs, &rhs) {
-            (Self::Int(l), Self::Float(_)) => Ok((
-                Self::Float(ConcreteFloat {
-                    items: l.items.iter().map(|v| *v as f64).collect(),
-                }),
-                rhs,
-                Dtype::Float,
-            )), // todo: implement Into for ConcreteArray
-            (Self::Float(_), Self::Int(r)) => Ok((
-                lhs,
-                Self::Float(ConcreteFloat {
-                    items: r.items.iter().map(|v| *v as f64).collect(),
-                }),
-                Dtype::Float,
-            )), // todo: implement Into for ConcreteArray
-            (x, y) if x.dtype() == y.dtype() => {
-                let dtype = lhs.dtype();
-                Ok((lhs, rhs, dtype))
-            }
-            _ => Err(PyValueError::new_err("PromotionError!")),
-        }
+    fn __eq__(&self, other: Bound<'_, PyAny>) -> PyResult<Self> {
+        self.compare_op(Operand::extract(&other)?, CompareOp::Eq)
     }
-}
 
-impl Add for Series {
-    type Output = PyResult<Self>;
+    fn __ne__(&self, other: Bound<'_, PyAny>) -> PyResult<Self> {
+        self.compare_op(Operand::extract(&other)?, CompareOp::Ne)
+    }
 
-    fn add(self, rhs: Self) -> Self::Output {
-        let (lhs, rhs, dtype) = Self::promote(self, rhs)?;
-        match dtype {
-            Dtype::Float => Ok(Self::Float(lhs.f64().unwrap() + rhs.f64().unwrap())),
-            Dtype::Int => Ok(Self::Int(lhs.i64().unwrap() + rhs.i64().unwrap())),
-            Dtype::String => Ok(Self::String(lhs.string().unwrap() + rhs.string().unwrap())),
-        }
+    fn pow(&self, other: Bound<'_, PyAny>) -> PyResult<Self> {
+        self.arithmetic(other, Self::pow_op)
     }
-}
-impl ConcreteArrayTrait for Series {
-    fn len(&self) -> usize {
-        match self {
-            Self::Int(concrete) => concrete.len(),
-            Self::Float(concrete) => concrete.len(),
-            Self::String(concrete) => concrete.len(),
-        }
+
+    #[pyo3(name = "mod")]
+    fn modulo(&self, other: Bound<'_, PyAny>) -> PyResult<Self> {
+        self.arithmetic(other, Self::mod_op)
     }
-}
 
-#[pyclass]
-struct DataFrame {
-    item: HashMap<String, Series>,
-}
+    fn __pow__(
+        &self,
+        other: Bound<'_, PyAny>,
+        _modulo: Option<Bound<'_, PyAny>>,
+    ) -> PyResult<Self> {
+        self.pow(other)
+    }
 
-#[pymethods]
-impl DataFrame {
-    #[new]
-    fn create(pydict: Bound<'_, PyDict>) -> PyResult<Self> {
-        let mut item: HashMap<String, Series> = HashMap::new();
-        let mut length: Option<usize> = None;
-        for (k, v) in pydict {
-            let col_name: String = k.extract()?;
-            let vec: Series = Series::create(v)?;
-            if *length.get_or_insert(vec.len()) != vec.len() {
-                return Err(PyValueError::new_err(
-                    "Incompatible length columns provided",
-                ));
+    /// Returns a new Series with values bounded to `[lower, upper]`; like every Series
+    /// transform it leaves `self` untouched so calls can be chained.
+    #[pyo3(signature = (lower = None, upper = None))]
+    fn clip(
+        &self,
+        lower: Option<Bound<'_, PyAny>>,
+        upper: Option<Bound<'_, PyAny>>,
+    ) -> PyResult<Self> {
+        let lower = lower.map(|v| Operand::extract(&v)).transpose()?;
+        let upper = upper.map(|v| Operand::extract(&v)).transpose()?;
+        self.clip_op(lower, upper)
+    }
+
+    /// Absolute value of each element of a numeric series. NaN stays NaN, and an Int
+    /// `i64::MIN`, whose absolute value has no Int, raises OverflowError.
+    fn abs(&self) -> PyResult<Self> {
+        self.numeric_map("abs", i64::checked_abs, f64::abs)
+    }
+
+    /// Negation of each element of a numeric series, so `-series` works. An Int `i64::MIN`,
+    /// whose negation has no Int, raises OverflowError.
+    fn __neg__(&self) -> PyResult<Self> {
+        self.numeric_map("neg", i64::checked_neg, |x| -x)
+    }
+
+    /// Float elements rounded to `ndigits` decimal places, halves to even as in Python's
+    /// `round`; a negative `ndigits` rounds to tens, hundreds and so on. Int series are
+    /// returned unchanged.
+    #[pyo3(signature = (ndigits = 0))]
+    fn round(&self, ndigits: i32) -> PyResult<Self> {
+        match self {
+            Self::Int(_) => Ok(self.clone()),
+            Self::Float(ConcreteFloat { items }) => {
+                let items = items
+                    .iter()
+                    .map(|x| {
+                        let rounded = round_half_even(*x, ndigits);
+                        if rounded.is_infinite() && x.is_finite() {
+                            Err(PyOverflowError::new_err(format!(
+                                "round({x}, {ndigits}) overflows Float"
+                            )))
+                        } else {
+                            Ok(rounded)
+                        }
+                    })
+                    .collect::<PyResult<_>>()?;
+                Ok(Self::Float(ConcreteFloat { items }).with_validity(self.validity().cloned()))
             }
-            item.insert(col_name, vec);
+            _ => Err(PyValueError::new_err(format!(
+                "round is undefined for {:?} Series",
+                self.dtype()
+            ))),
         }
-        Ok(Self { item })
-    }
-    fn __repr__(&self) -> String {
-        format!("DataFrame({:?})", self.item)
     }
 
-    fn __str__(&self) -> String {
-        format!("DataFrame({:?})", self.item)
+    /// The elements as a list of Python scalars, None for nulls; the inverse of the
+    /// constructor.
+    fn to_list(&self, py: Python<'_>) -> Py<PyList> {
+        let values = (0..self.len()).map(|index| self.get(py, index));
+        PyList::new_bound(py, values).unbind()
     }
 
-    fn __len__(&self) -> usize {
-        self.item.values().next().map_or(0, |s| s.len())
+    /// New series of `func` called on each element, with the dtype inferred from the results
+    /// as in the constructor. This calls back into Python once per element, so it is the slow
+    /// path next to the vectorised operators.
+    fn apply(&self, py: Python<'_>, func: Bound<'_, PyAny>) -> PyResult<Self> {
+        let values = (0..self.len())
+            .map(|index| func.call1((self.get(py, index),)))
+            .collect::<PyResult<Vec<_>>>()?;
+        Self::create(PyList::new_bound(py, values).into_any())
     }
 
-    fn __getitem__(&self, index: Bound<'_, PyString>) -> PyResult<Series> {
-        let key: String = index.extract()?;
-        // TODO: keep data in rust? Make Series PyClass compatible with print for viewing in python
-        let result = self
-            .item
-            .get(&key)
-            .ok_or(PyKeyError::new_err("Unrecognised key"))?;
-        Ok(result.clone())
+    /// Bool mask of whether each element is among `values`, any iterable such as a list or
+    /// set. Candidates of another type never match, except that Int and Float compare
+    /// numerically; NaN matches NaN, and nulls match nothing.
+    fn isin(&self, values: Bound<'_, PyAny>) -> PyResult<Self> {
+        if values.is_instance_of::<PyString>() {
+            return Err(PyTypeError::new_err(
+                "isin takes a collection of values, not a str",
+            ));
+        }
+        let candidates = values.iter()?.collect::<PyResult<Vec<_>>>()?;
+        // Bools are Python ints, so they are only candidates for a Bool series.
+        let (bools, others): (Vec<_>, Vec<_>) = candidates
+            .iter()
+            .partition(|c| c.is_instance_of::<PyBool>());
+        // 0.0 and -0.0 are equal but differ in bits.
+        let float_key = |x: f64| if x == 0.0 { 0.0f64 } else { x }.to_bits();
+        let valid = |i: usize| self.is_valid(i);
+        let items: Buffer<bool> = match self {
+            Self::Int(ConcreteInt { items }) => {
+                // Going through f64 would let a large int match its rounded neighbours, so
+                // only float candidates take that path.
+                let set: HashSet<i64> = others
+                    .iter()
+                    .filter_map(|c| match c.downcast::<PyFloat>() {
+                        Ok(x) => {
+                            let x = x.value();
+                            (x.fract() == 0.0 && x.abs() < 2f64.powi(63)).then_some(x as i64)
+                        }
+                        Err(_) => c.extract::<i64>().ok(),
+                    })
+                    .collect();
+                (0..items.len())
+                    .map(|i| valid(i) && set.contains(&items[i]))
+                    .collect()
+            }
+            Self::Float(ConcreteFloat { items }) => {
+                let set: HashSet<u64> = others
+                    .iter()
+                    .filter_map(|c| c.extract::<f64>().ok())
+                    .map(float_key)
+                    .collect();
+                (0..items.len())
+                    .map(|i| valid(i) && set.contains(&float_key(items[i])))
+                    .collect()
+            }
+            Self::String(ConcreteString { items }) => {
+                let set: HashSet<String> = candidates
+                    .iter()
+                    .filter_map(|c| c.extract::<String>().ok())
+                    .collect();
+                (0..items.len())
+                    .map(|i| valid(i) && set.contains(&items[i]))
+                    .collect()
+            }
+            Self::Bool(ConcreteBool { items }) => {
+                let set: HashSet<bool> = bools
+                    .iter()
+                    .filter_map(|c| c.extract::<bool>().ok())
+                    .collect();
+                (0..items.len())
+                    .map(|i| valid(i) && set.contains(&items[i]))
+                    .collect()
+            }
+        };
+        Ok(Self::Bool(ConcreteBool { items }))
     }
 
-    fn __setitem__(&mut self, index: Bound<'_, PyString>, value: Bound<'_, PyAny>) -> PyResult<()> {
-        let key: String = index.extract()?;
-        let value: Series = Series::create(value)?;
-        let current_length = self.__len__();
-        if current_length != 0 && (current_length != value.len()) {
-            return Err(PyValueError::new_err(
-                "Incompatible length series inserted!",
-            ));
+    /// New series of each element's value in `mapping`, with the dtype inferred from the
+    /// values as in the constructor. Elements missing from `mapping` become nulls, as does
+    /// everything if nothing maps, giving a Float series.
+    fn map(&self, py: Python<'_>, mapping: Bound<'_, PyDict>) -> PyResult<Self> {
+        let mut values = vec![];
+        let mut positions = vec![];
+        for index in 0..self.len() {
+            let value = if self.is_valid(index) {
+                mapping.get_item(self.get(py, index))?
+            } else {
+                None
+            };
+            positions.push(value.is_some().then_some(values.len()));
+            values.extend(value);
         }
-        self.item.insert(key, value);
-        Ok(())
+        let mapped = if values.is_empty() {
+            Self::Float(ConcreteFloat {
+                items: vec![].into(),
+            })
+        } else {
+            Self::create(PyList::new_bound(py, values).into_any())
+                .map_err(|_| PyValueError::new_err("Mapped values do not share a Series dtype"))?
+        };
+        Ok(mapped.take_or_null(&positions))
     }
 
-    #[staticmethod]
-    fn from_csv(path: Bound<'_, PyString>) -> PyResult<Self> {
-        let py = path.py();
-        let path: String = path.extract()?;
-        py.allow_threads(|| {
-            let file = File::open(path)?;
-            let reader = BufReader::new(file);
-            // TODO: move out of Box<dyn Error>
-            match Self::from_csv_reader(reader) {
-                Err(_) => Err(PyValueError::new_err("Failed to parse appropriately")),
-                Ok(res) => Ok(res),
+    /// Converts to `"int"`, `"float"` or `"string"`. Floats truncate towards zero when cast to
+    /// int and Strings must parse as the target; the first failure raises. Casting to the
+    /// current dtype is a clone sharing the same buffer.
+    fn astype(&self, dtype: &str) -> PyResult<Self> {
+        let target = match dtype {
+            "int" => Dtype::Int,
+            "float" => Dtype::Float,
+            "string" => Dtype::String,
+            _ => {
+                return Err(PyValueError::new_err(format!(
+                    "Unknown dtype {dtype:?}; expected \"int\", \"float\" or \"string\""
+                )))
+            }
+        };
+        if target == self.dtype() {
+            return Ok(self.clone());
+        }
+        // Null slots hold placeholders that needn't convert.
+        let valid = |i: usize| self.is_valid(i);
+        let cast = match (self, target) {
+            (Self::Int(ConcreteInt { items }), Dtype::Float) => Self::Float(ConcreteFloat {
+                items: items.iter().map(|x| *x as f64).collect(),
+            }),
+            (Self::Bool(ConcreteBool { items }), Dtype::Int) => Self::Int(ConcreteInt {
+                items: items.iter().map(|x| i64::from(*x)).collect(),
+            }),
+            (Self::Bool(ConcreteBool { items }), Dtype::Float) => Self::Float(ConcreteFloat {
+                items: items.iter().map(|x| f64::from(u8::from(*x))).collect(),
+            }),
+            (Self::Float(ConcreteFloat { items }), Dtype::Int) => Self::Int(ConcreteInt {
+                items: items
+                    .iter()
+                    .enumerate()
+                    .map(|(i, x)| match x.trunc() {
+                        _ if !valid(i) => Ok(0),
+                        t if t >= i64::MIN as f64 && t < i64::MAX as f64 => Ok(t as i64),
+                        _ => Err(PyValueError::new_err(format!("Cannot convert {x} to Int"))),
+                    })
+                    .collect::<PyResult<_>>()?,
+            }),
+            (Self::String(ConcreteString { items }), Dtype::Int) => Self::Int(ConcreteInt {
+                items: items
+                    .iter()
+                    .enumerate()
+                    .map(|(i, s)| match s.parse() {
+                        _ if !valid(i) => Ok(0),
+                        Ok(x) => Ok(x),
+                        Err(_) => Err(PyValueError::new_err(format!("{s:?} is not a valid Int"))),
+                    })
+                    .collect::<PyResult<_>>()?,
+            }),
+            (Self::String(ConcreteString { items }), Dtype::Float) => Self::Float(ConcreteFloat {
+                items: items
+                    .iter()
+                    .enumerate()
+                    .map(|(i, s)| match s.parse() {
+                        _ if !valid(i) => Ok(f64::NAN),
+                        Ok(x) => Ok(x),
+                        Err(_) => Err(PyValueError::new_err(format!("{s:?} is not a valid Float"))),
+                    })
+                    .collect::<PyResult<_>>()?,
+            }),
+            (_, Dtype::String) => Self::String(ConcreteString {
+                items: (0..self.len())
+                    .map(|i| match self {
+                        Self::Int(ConcreteInt { items }) => items[i].to_string(),
+                        Self::Float(ConcreteFloat { items }) => format!("{:?}", items[i]),
+                        Self::Bool(ConcreteBool { items }) => items[i].to_string(),
+                        Self::String(ConcreteString { items }) => items[i].clone(),
+                    })
+                    .collect(),
+            }),
+            _ => unreachable!("every other pair shares a dtype"),
+        };
+        Ok(cast.with_validity(self.validity().cloned()))
+    }
+
+    fn __mod__(&self, other: Bound<'_, PyAny>) -> PyResult<Self> {
+        self.modulo(other)
+    }
+
+    /// Insertion index (or Int Series of indices) keeping the series sorted. The series must
+    /// already be sorted ascending; `side="right"` inserts after equal elements.
+    #[pyo3(signature = (value, side = "left"))]
+    fn searchsorted(&self, value: Bound<'_, PyAny>, side: &str) -> PyResult<PyObject> {
+        let py = value.py();
+        let right = match side {
+            "left" => false,
+            "right" => true,
+            _ => return Err(PyValueError::new_err("side must be \"left\" or \"right\"")),
+        };
+        let values: Vec<Operand> = match Operand::extract(&value)? {
+            Operand::Series(Self::Int(ConcreteInt { items })) => {
+                items.into_iter().map(Operand::Int).collect()
+            }
+            Operand::Series(Self::Float(ConcreteFloat { items })) => {
+                items.into_iter().map(Operand::Float).collect()
+            }
+            Operand::Series(Self::String(_)) => {
+                return Err(PyValueError::new_err(
+                    "searchsorted requires a numeric series and numeric values",
+                ))
+            }
+            scalar => return Ok(self.insertion_point(&scalar, right)?.into_py(py)),
+        };
+        let items = values
+            .iter()
+            .map(|v| self.insertion_point(v, right).map(|i| i as i64))
+            .collect::<PyResult<_>>()?;
+        Ok(Self::Int(ConcreteInt { items }).into_py(py))
+    }
+
+    /// Running total; Int sums that leave the i64 range raise an overflow error.
+    fn cumsum(&self) -> PyResult<Self> {
+        self.cumulative(
+            |a, x| {
+                a.checked_add(x)
+                    .ok_or_else(|| PyOverflowError::new_err(format!("{a} + {x} overflows Int")))
+            },
+            |a, x| a + x,
+        )
+    }
+
+    /// Running product; Int products that leave the i64 range raise an overflow error.
+    fn cumprod(&self) -> PyResult<Self> {
+        self.cumulative(
+            |a, x| {
+                a.checked_mul(x)
+                    .ok_or_else(|| PyOverflowError::new_err(format!("{a} * {x} overflows Int")))
+            },
+            |a, x| a * x,
+        )
+    }
+
+    /// Running maximum; NaN elements are skipped.
+    fn cummax(&self) -> PyResult<Self> {
+        self.cumulative(|a, x| Ok(a.max(x)), f64::max)
+    }
+
+    /// Running minimum; NaN elements are skipped.
+    fn cummin(&self) -> PyResult<Self> {
+        self.cumulative(|a, x| Ok(a.min(x)), f64::min)
+    }
+
+    /// Sum of a numeric series as a Python int or float; an empty series sums to zero.
+    fn sum(&self, py: Python<'_>) -> PyResult<PyObject> {
+        self.reduce(py, Aggregation::Sum)
+    }
+
+    /// Smallest element of a numeric or String series. NaN elements are skipped, so a Float
+    /// series of only NaN gives NaN.
+    fn min(&self, py: Python<'_>) -> PyResult<PyObject> {
+        self.reduce(py, Aggregation::Min)
+    }
+
+    /// Largest element of a numeric or String series. NaN elements are skipped, so a Float
+    /// series of only NaN gives NaN.
+    fn max(&self, py: Python<'_>) -> PyResult<PyObject> {
+        self.reduce(py, Aggregation::Max)
+    }
+
+    /// Arithmetic mean of a numeric series, skipping nulls; Int elements are summed as f64.
+    fn mean(&self) -> PyResult<f64> {
+        let (sum, len) = match &self.drop_nulls() {
+            Self::Int(ConcreteInt { items }) => {
+                (items.iter().map(|v| *v as f64).sum::<f64>(), items.len())
+            }
+            Self::Float(ConcreteFloat { items }) => (items.iter().sum::<f64>(), items.len()),
+            Self::String(_) | Self::Bool(_) => {
+                return Err(PyTypeError::new_err(format!(
+                    "Cannot Mean a {:?} series",
+                    self.dtype()
+                )))
+            }
+        };
+        if len == 0 {
+            return Err(PyValueError::new_err("mean of empty series"));
+        }
+        Ok(sum / len as f64)
+    }
+
+    /// Middle value of a numeric series, the mean of the two middle values for an even
+    /// count; `quantile(0.5)`.
+    fn median(&self) -> PyResult<f64> {
+        self.quantile(0.5)
+    }
+
+    /// Value a fraction `q` of the way through the sorted elements, interpolating linearly
+    /// between the two elements straddling position `q * (n - 1)`. Nulls and NaN are skipped.
+    fn quantile(&self, q: f64) -> PyResult<f64> {
+        if !(0.0..=1.0).contains(&q) {
+            return Err(PyValueError::new_err(format!(
+                "quantile {q} is outside [0, 1]"
+            )));
+        }
+        let valid = |i: &usize| self.is_valid(*i);
+        let mut values: Vec<f64> = match self {
+            Self::Int(ConcreteInt { items }) => (0..items.len())
+                .filter(valid)
+                .map(|i| items[i] as f64)
+                .collect(),
+            Self::Float(ConcreteFloat { items }) => (0..items.len())
+                .filter(valid)
+                .map(|i| items[i])
+                .filter(|x| !x.is_nan())
+                .collect(),
+            Self::String(_) | Self::Bool(_) => {
+                return Err(PyTypeError::new_err(format!(
+                    "Cannot Quantile a {:?} series",
+                    self.dtype()
+                )))
+            }
+        };
+        if values.is_empty() {
+            return Err(PyValueError::new_err("quantile of empty series"));
+        }
+        values.sort_by(f64::total_cmp);
+        let position = q * (values.len() - 1) as f64;
+        let (lower, fraction) = (position.floor() as usize, position.fract());
+        let (low, high) = (values[lower], values[(lower + 1).min(values.len() - 1)]);
+        if low == high {
+            return Ok(low);
+        }
+        Ok(low * (1.0 - fraction) + high * fraction)
+    }
+
+    /// Variance with `ddof` delta degrees of freedom: 0 for population, 1 for sample. Nulls
+    /// are skipped.
+    #[pyo3(signature = (ddof = 1))]
+    fn var(&self, ddof: usize) -> PyResult<f64> {
+        // Sum and sum of squares in one pass.
+        let moments = |values: &mut dyn Iterator<Item = f64>| {
+            values.fold((0usize, 0.0, 0.0), |(n, sum, sum_sq), x| {
+                (n + 1, sum + x, sum_sq + x * x)
+            })
+        };
+        let (n, sum, sum_sq) = match &self.drop_nulls() {
+            Self::Int(ConcreteInt { items }) => moments(&mut items.iter().map(|v| *v as f64)),
+            Self::Float(ConcreteFloat { items }) => moments(&mut items.iter().copied()),
+            Self::String(_) | Self::Bool(_) => {
+                return Err(PyTypeError::new_err(format!(
+                    "Cannot Var a {:?} series",
+                    self.dtype()
+                )))
             }
+        };
+        if n <= ddof {
+            return Err(PyValueError::new_err(format!(
+                "var of {n} elements with ddof={ddof} is undefined"
+            )));
+        }
+        // Cancellation can leave a tiny negative for constant series.
+        Ok(((sum_sq - sum * sum / n as f64) / (n - ddof) as f64).max(0.0))
+    }
+
+    /// Standard deviation, the square root of `var(ddof)`.
+    #[pyo3(signature = (ddof = 1))]
+    fn std(&self, ddof: usize) -> PyResult<f64> {
+        Ok(self.var(ddof)?.sqrt())
+    }
+
+    /// Position of the first smallest element; NaN elements are skipped.
+    fn argmin(&self) -> PyResult<usize> {
+        self.arg_extreme(Ordering::Less)
+    }
+
+    /// Position of the first largest element; NaN elements are skipped.
+    fn argmax(&self) -> PyResult<usize> {
+        self.arg_extreme(Ordering::Greater)
+    }
+
+    /// Sorted copy of the series. The sort is stable, NaN and nulls sort last in either
+    /// direction, and false sorts before true.
+    #[pyo3(signature = (ascending = true))]
+    fn sort(&self, ascending: bool) -> Self {
+        self.take(&self.sorted_positions(ascending))
+    }
+
+    /// Int series of the positions that would sort the series, ordered as by `sort`.
+    #[pyo3(signature = (ascending = true))]
+    fn argsort(&self, ascending: bool) -> Self {
+        Self::Int(ConcreteInt {
+            items: self
+                .sorted_positions(ascending)
+                .into_iter()
+                .map(|i| i as i64)
+                .collect(),
         })
     }
-}
 
-impl DataFrame {
-    // Ideally use serde but I'm on a flight and cannot `cargo add`
-    fn from_csv_reader<R: Read>(buf_reader: BufReader<R>) -> Result<Self, Box<dyn Error>> {
-        let mut iter = buf_reader.lines();
-        if let Some(Ok(header_elements)) = iter.next() {
-            let header_elements: Vec<String> = header_elements
-                .trim_matches(',')
-                .split(',')
-                .map(|s| s.trim().to_owned())
-                .collect();
-            let mut str_items: HashMap<&str, Vec<String>> = header_elements
-                .iter()
-                .map(|s| (s.as_str(), vec![]))
-                .collect();
-            for line in iter {
-                let line = line?;
-                let split_tokens = line.trim_matches(',').split(',');
-                let mut count = 0;
-                for (key, tok) in header_elements.iter().zip(split_tokens) {
-                    str_items
-                        .get_mut(key.as_str())
-                        .unwrap()
-                        .push(tok.to_owned());
-                    count += 1;
-                }
-                if count != header_elements.len() {
-                    return Err("Incompatible row length with number of columns in header".into());
+    /// Bool mask of elements within `[low, high]`. `inclusive` is one of "both", "neither",
+    /// "left" or "right"; String series compare lexicographically.
+    #[pyo3(signature = (low, high, inclusive = "both"))]
+    fn between(
+        &self,
+        low: Bound<'_, PyAny>,
+        high: Bound<'_, PyAny>,
+        inclusive: &str,
+    ) -> PyResult<Self> {
+        let (left, right) = match inclusive {
+            "both" => (true, true),
+            "neither" => (false, false),
+            "left" => (true, false),
+            "right" => (false, true),
+            _ => {
+                return Err(PyValueError::new_err(
+                    "inclusive must be \"both\", \"neither\", \"left\" or \"right\"",
+                ))
+            }
+        };
+        self.between_mask(
+            Operand::extract(&low)?,
+            Operand::extract(&high)?,
+            left,
+            right,
+        )
+    }
+
+    /// Float series rescaled to [0, 1]; a constant series becomes all zeros.
+    fn minmax_scale(&self) -> PyResult<Self> {
+        self.minmax_scaled()
+    }
+
+    /// Float series with mean 0 and (population) standard deviation 1; a constant series
+    /// becomes all zeros.
+    fn zscore(&self) -> PyResult<Self> {
+        self.zscored()
+    }
+
+    /// pyarrow array (Int64, Float64 or Utf8) holding a copy of the values.
+    #[cfg(feature = "arrow")]
+    fn to_arrow(&self, py: Python<'_>) -> PyResult<PyObject> {
+        use arrow::pyarrow::ToPyArrow;
+        arrow_bridge::to_arrow(self).to_data().to_pyarrow(py)
+    }
+
+    #[cfg(feature = "arrow")]
+    #[staticmethod]
+    fn from_arrow(array: Bound<'_, PyAny>) -> PyResult<Self> {
+        use arrow::pyarrow::FromPyArrow;
+        let data = arrow::array::ArrayData::from_pyarrow_bound(&array)?;
+        arrow_bridge::from_arrow(arrow::array::make_array(data).as_ref())
+    }
+
+    /// Copy with nulls, and NaN in Float series, replaced by the scalar `value`. Filling an
+    /// Int series with a float gives a Float series. Without any gaps this is a clone.
+    fn fillna(&self, value: Bound<'_, PyAny>) -> PyResult<Self> {
+        let gap = |i: usize| match self {
+            Self::Float(ConcreteFloat { items }) if items[i].is_nan() => true,
+            _ => !self.is_valid(i),
+        };
+        if !(0..self.len()).any(gap) {
+            return Ok(self.clone());
+        }
+        let mismatch = || {
+            PyValueError::new_err(format!(
+                "Cannot fill a {:?} Series with {}",
+                self.dtype(),
+                value.get_type()
+            ))
+        };
+        let is_bool = value.is_instance_of::<PyBool>();
+        let filled = match self {
+            Self::Int(ConcreteInt { items }) if !is_bool => match value.extract::<i64>() {
+                Ok(fill) => Self::Int(ConcreteInt {
+                    items: (0..self.len())
+                        .map(|i| if gap(i) { fill } else { items[i] })
+                        .collect(),
+                }),
+                Err(_) => {
+                    let fill: f64 = value.extract().map_err(|_| mismatch())?;
+                    Self::Float(ConcreteFloat {
+                        items: (0..self.len())
+                            .map(|i| if gap(i) { fill } else { items[i] as f64 })
+                            .collect(),
+                    })
                 }
+            },
+            Self::Float(ConcreteFloat { items }) if !is_bool => {
+                let fill: f64 = value.extract().map_err(|_| mismatch())?;
+                Self::Float(ConcreteFloat {
+                    items: (0..self.len())
+                        .map(|i| if gap(i) { fill } else { items[i] })
+                        .collect(),
+                })
             }
-            let mut item: HashMap<String, Series> = HashMap::new();
-            for (k, v) in str_items.into_iter() {
-                let series = Series::from_untyped(v.as_slice())?;
-                item.insert(k.to_owned(), series);
+            Self::String(ConcreteString { items }) => {
+                let fill: String = value.extract().map_err(|_| mismatch())?;
+                Self::String(ConcreteString {
+                    items: (0..self.len())
+                        .map(|i| {
+                            if gap(i) {
+                                fill.clone()
+                            } else {
+                                items[i].clone()
+                            }
+                        })
+                        .collect(),
+                })
             }
+            Self::Bool(ConcreteBool { items }) if is_bool => {
+                let fill: bool = value.extract()?;
+                Self::Bool(ConcreteBool {
+                    items: (0..self.len())
+                        .map(|i| if gap(i) { fill } else { items[i] })
+                        .collect(),
+                })
+            }
+            _ => return Err(mismatch()),
+        };
+        Ok(filled)
+    }
 
-            return Ok(Self { item });
+    /// Upper-cased copy of a String series.
+    fn str_upper(&self) -> PyResult<Self> {
+        self.map_strings("str_upper", |items| {
+            Self::String(ConcreteString {
+                items: items.iter().map(|s| s.to_uppercase()).collect(),
+            })
+        })
+    }
+
+    /// Lower-cased copy of a String series.
+    fn str_lower(&self) -> PyResult<Self> {
+        self.map_strings("str_lower", |items| {
+            Self::String(ConcreteString {
+                items: items.iter().map(|s| s.to_lowercase()).collect(),
+            })
+        })
+    }
+
+    /// Bool series of whether each String element contains `pat`.
+    fn str_contains(&self, pat: &str) -> PyResult<Self> {
+        self.map_strings("str_contains", |items| {
+            Self::Bool(ConcreteBool {
+                items: items.iter().map(|s| s.contains(pat)).collect(),
+            })
+        })
+    }
+
+    /// Bool series of whether each String element starts with `pat`.
+    fn str_startswith(&self, pat: &str) -> PyResult<Self> {
+        self.map_strings("str_startswith", |items| {
+            Self::Bool(ConcreteBool {
+                items: items.iter().map(|s| s.starts_with(pat)).collect(),
+            })
+        })
+    }
+
+    /// Int series of each String element's length in characters.
+    fn str_len(&self) -> PyResult<Self> {
+        self.map_strings("str_len", |items| {
+            Self::Int(ConcreteInt {
+                items: items.iter().map(|s| s.chars().count() as i64).collect(),
+            })
+        })
+    }
+
+    /// Joins each String element with the corresponding one of `other`, `sep` between them.
+    #[pyo3(signature = (other, sep = ""))]
+    fn str_cat(&self, other: Series, sep: &str) -> PyResult<Self> {
+        let validity = self.combined_validity(&Operand::Series(other.clone()));
+        match (self, &other) {
+            _ if self.len() != other.len() => Err(PyValueError::new_err(format!(
+                "Length mismatch: {} and {}",
+                self.len(),
+                other.len()
+            ))),
+            (Self::String(lhs), Self::String(rhs)) => {
+                Ok(Self::String(lhs.cat(rhs, sep)).with_validity(validity))
+            }
+            _ => Err(PyValueError::new_err(format!(
+                "Cannot str_cat {:?} and {:?} Series",
+                self.dtype(),
+                other.dtype()
+            ))),
+        }
+    }
+
+    /// Stacks `series` end to end. Int and Float series mix by promoting to Float; any other
+    /// mix of dtypes raises.
+    #[staticmethod]
+    fn concat(series: Vec<Series>) -> PyResult<Self> {
+        let first = series
+            .first()
+            .ok_or_else(|| PyValueError::new_err("Cannot concat an empty list of Series"))?;
+        let dtype = series
+            .iter()
+            .try_fold(first.dtype(), |dtype, s| match (dtype, s.dtype()) {
+                (a, b) if a == b => Ok(a),
+                (Dtype::Int | Dtype::Float, Dtype::Int | Dtype::Float) => Ok(Dtype::Float),
+                (a, b) => Err(PyValueError::new_err(format!(
+                    "Cannot concat {a:?} and {b:?} Series"
+                ))),
+            })?;
+        let joined = match dtype {
+            Dtype::Int => Self::Int(ConcreteInt {
+                items: series
+                    .iter()
+                    .flat_map(|s| match s {
+                        Self::Int(ConcreteInt { items }) => items.iter().copied(),
+                        _ => unreachable!("dtypes were checked"),
+                    })
+                    .collect(),
+            }),
+            Dtype::Float => Self::Float(ConcreteFloat {
+                items: series
+                    .iter()
+                    .map(Self::f64_values)
+                    .collect::<PyResult<Vec<_>>>()?
+                    .concat()
+                    .into(),
+            }),
+            Dtype::String => Self::String(ConcreteString {
+                items: series
+                    .iter()
+                    .flat_map(|s| match s {
+                        Self::String(ConcreteString { items }) => items.iter().cloned(),
+                        _ => unreachable!("dtypes were checked"),
+                    })
+                    .collect(),
+            }),
+            Dtype::Bool => Self::Bool(ConcreteBool {
+                items: series
+                    .iter()
+                    .flat_map(|s| match s {
+                        Self::Bool(ConcreteBool { items }) => items.iter().copied(),
+                        _ => unreachable!("dtypes were checked"),
+                    })
+                    .collect(),
+            }),
+        };
+        let validity = series.iter().any(|s| s.validity().is_some()).then(|| {
+            Arc::new(
+                series
+                    .iter()
+                    .flat_map(|s| (0..s.len()).map(|i| s.is_valid(i)))
+                    .collect(),
+            )
+        });
+        Ok(joined.with_validity(validity))
+    }
+
+    /// Distinct elements in the order they first appear. Floats are compared by bit pattern,
+    /// so repeated NaNs collapse to one while 0.0 and -0.0 stay distinct. Nulls collapse to
+    /// a single null.
+    fn unique(&self) -> Self {
+        let positions: Vec<usize> = self.occurrences().into_iter().map(|(i, _)| i).collect();
+        self.take(&positions)
+    }
+
+    /// Frame of each distinct element (`value`) and how often it occurs (`count`), most
+    /// frequent first with ties in ascending value order. Floats are grouped by bit pattern
+    /// as in `unique`, so NaNs count together and sort after other values on ties. Nulls are
+    /// not counted.
+    fn value_counts(&self) -> DataFrame {
+        let mut occurrences: Vec<(usize, usize)> = self
+            .occurrences()
+            .into_iter()
+            .filter(|(i, _)| self.is_valid(*i))
+            .collect();
+        occurrences.sort_by(|(i, m), (j, n)| n.cmp(m).then_with(|| self.cmp_rows(*i, *j, true)));
+        let (positions, counts): (Vec<usize>, Vec<i64>) =
+            occurrences.into_iter().map(|(i, n)| (i, n as i64)).unzip();
+        DataFrame {
+            item: IndexMap::from([
+                ("value".to_owned(), self.take(&positions)),
+                (
+                    "count".to_owned(),
+                    Self::Int(ConcreteInt {
+                        items: counts.into(),
+                    }),
+                ),
+            ]),
+        }
+    }
+
+    /// Most frequently occurring value, or a Series of every tied value in sorted order.
+    /// Float values are compared with exact equality.
+    fn mode(&self, py: Python<'_>) -> PyObject {
+        let modes = self.modes();
+        match &modes {
+            Series::Int(ConcreteInt { items }) if items.len() == 1 => items[0].into_py(py),
+            Series::Float(ConcreteFloat { items }) if items.len() == 1 => items[0].into_py(py),
+            Series::String(ConcreteString { items }) if items.len() == 1 => {
+                items[0].clone().into_py(py)
+            }
+            Series::Bool(ConcreteBool { items }) if items.len() == 1 => items[0].into_py(py),
+            _ => modes.into_py(py),
+        }
+    }
+}
+
+#[derive(PartialEq, Eq, Debug, Clone, Copy, Hash)]
+enum Dtype {
+    Int,
+    Float,
+    String,
+    Bool,
+}
+
+impl Dtype {
+    /// Inverse of the `Debug` name, as written by `DataFrame.schema`.
+    fn parse(s: &str) -> PyResult<Self> {
+        match s {
+            "Int" => Ok(Self::Int),
+            "Float" => Ok(Self::Float),
+            "String" => Ok(Self::String),
+            "Bool" => Ok(Self::Bool),
+            _ => Err(PyValueError::new_err(format!(
+                "Unknown dtype {s:?}; expected \"Int\", \"Float\", \"String\" or \"Bool\""
+            ))),
+        }
+    }
+
+    fn infer(s: &str) -> Option<Self> {
+        if s.len() == 1 && s == "\"" {
+            return None;
+        }
+        let mut cs = s.chars();
+        let first = cs.next()?;
+        let last = cs.next_back();
+        if first == '\"' && last.is_some() && last.unwrap() == '\"' {
+            return Some(Self::String);
+        }
+        if s == "true" || s == "false" {
+            return Some(Self::Bool);
+        }
+        if matches!(
+            s.to_ascii_lowercase().as_str(),
+            "inf" | "-inf" | "infinity" | "-infinity" | "nan"
+        ) {
+            return Some(Self::Float);
+        }
+
+        fn acceptable_char(c: char) -> bool {
+            c.is_ascii_digit() || c == '.' || c == '-'
+        }
+        // `{:?}`, as used by `to_csv`, writes very large and small floats with an exponent.
+        if s.contains(['e', 'E'])
+            && s.chars().all(|c| acceptable_char(c) || "eE+".contains(c))
+            && s.parse::<f64>().is_ok()
+        {
+            return Some(Self::Float);
+        }
+        if s.chars().all(acceptable_char)
+            && acceptable_char(first)
+            && last.map_or_else(|| true, acceptable_char)
+        {
+            if s.contains('.') {
+                Some(Self::Float)
+            } else {
+                Some(Self::Int)
+            }
         } else {
-            return Err("Empty buffer".into());
+            None
         }
     }
+
+    /// Dtype `from_csv` reads the cell `s` as: the inferred one, or String for bare text. Only
+    /// a malformed quoted field has none.
+    fn of_cell(s: &str) -> Option<Self> {
+        Self::infer(s).or_else(|| (!s.starts_with('"')).then_some(Self::String))
+    }
 }
 
-// impl IntoPy<PyObject> for Series {
-//     fn into_py(self, py: Python<'_>) -> PyObject {
-//         match self {
-//             Self::Int(concrete) => concrete.into_py(py),
-//             Self::Float(concrete) => concrete.into_py(py),
-//             Self::String(concrete) => concrete.into_py(py),
-//         }
-//     }
-// }
+/// `x` rounded to `ndigits` decimal places, ties to even. Non-negative `ndigits` go through
+/// `format!`, which rounds the exact binary value correctly as Python does, so 2.675 (really
+/// 2.67499...) rounds down.
+fn round_half_even(x: f64, ndigits: i32) -> f64 {
+    // Every finite f64 written to 17 significant digits reads back unchanged, and the
+    // smallest subnormal's first significant digit is the 324th decimal place.
+    if !x.is_finite() || ndigits > 340 {
+        return x;
+    }
+    if ndigits >= 0 {
+        return format!("{x:.*}", ndigits as usize).parse().unwrap();
+    }
+    let scale = 10f64.powi(-ndigits);
+    if scale.is_infinite() {
+        return 0.0f64.copysign(x);
+    }
+    (x / scale).round_ties_even() * scale
+}
 
-#[pymodule]
-fn dfrs(m: &Bound<'_, PyModule>) -> PyResult<()> {
-    m.add_class::<DataFrame>()?;
-    m.add_class::<Series>()?;
-    Ok(())
+/// A JSON scalar written as the CSV cell `from_csv` would read back as the same value.
+fn json_cell(value: &json::Value) -> Result<String, String> {
+    match value {
+        json::Value::Null => Ok(String::new()),
+        json::Value::Bool(b) => Ok(b.to_string()),
+        json::Value::Int(i) => Ok(i.to_string()),
+        json::Value::Float(x) => Ok(format!("{x:?}")),
+        json::Value::String(s) => Ok(format!("\"{}\"", s.replace('"', "\"\""))),
+        json::Value::Array(_) | json::Value::Object(_) => {
+            Err("nested arrays and objects are unsupported".to_owned())
+        }
+    }
+}
+
+/// Contents of a quoted CSV field, with escaped `""` quotes unescaped.
+fn unquote(field: &str) -> String {
+    field[1..field.len() - 1].replace("\"\"", "\"")
+}
+
+/// Position of a Python-style `index` into `len` elements, counting from the end when
+/// negative.
+fn resolve_index(index: isize, len: usize) -> PyResult<usize> {
+    let position = if index < 0 {
+        len.checked_sub(index.unsigned_abs())
+    } else {
+        Some(index as usize).filter(|i| *i < len)
+    };
+    position.ok_or_else(|| {
+        PyIndexError::new_err(format!("index {index} is out of range for length {len}"))
+    })
+}
+
+/// Index of the first element ordered `wanted` relative to every other, skipping elements
+/// that don't compare (NaN).
+fn first_extreme<T: PartialOrd>(items: &[T], wanted: Ordering) -> Option<usize> {
+    let mut best: Option<usize> = None;
+    for (i, x) in items.iter().enumerate() {
+        if x.partial_cmp(x).is_none() {
+            continue;
+        }
+        if best.is_none_or(|b| x.partial_cmp(&items[b]) == Some(wanted)) {
+            best = Some(i);
+        }
+    }
+    best
+}
+
+/// Position of the first element with each distinct `key` and how many elements share
+/// it, in first-seen order.
+fn first_occurrences<'a, T, K: Hash + Eq>(
+    items: &'a [T],
+    key: impl Fn(usize, &'a T) -> K,
+) -> Vec<(usize, usize)> {
+    let mut seen: HashMap<K, usize> = HashMap::new();
+    let mut occurrences: Vec<(usize, usize)> = vec![];
+    for (i, item) in items.iter().enumerate() {
+        let slot = *seen.entry(key(i, item)).or_insert_with(|| {
+            occurrences.push((i, 0));
+            occurrences.len() - 1
+        });
+        occurrences[slot].1 += 1;
+    }
+    occurrences
+}
+
+/// Values of `items` sharing the highest frequency, in first-seen order.
+fn most_frequent<T: Clone, K: Hash + Eq>(items: &[T], key: impl Fn(&T) -> K) -> Vec<T> {
+    let mut counts: HashMap<K, usize> = HashMap::new();
+    let mut distinct: Vec<&T> = vec![];
+    for item in items {
+        let count = counts.entry(key(item)).or_insert(0);
+        if *count == 0 {
+            distinct.push(item);
+        }
+        *count += 1;
+    }
+    let highest = counts.values().copied().max().unwrap_or(0);
+    distinct
+        .into_iter()
+        .filter(|item| counts[&key(item)] == highest)
+        .cloned()
+        .collect()
+}
+
+fn collect_early_exit<'s, Input: 's, Return, F, E, It>(it: It, func: F) -> Result<Vec<Return>, E>
+where
+    It: Iterator<Item = &'s Input>,
+    F: Fn(&Input) -> Result<Return, E>,
+{
+    let mut vec = Vec::new();
+    for item in it {
+        vec.push(func(item)?);
+    }
+    Ok(vec)
+}
+
+impl Series {
+    /// Common dtype of the cells, ignoring empty (null) ones; Float if every cell is null.
+    /// Errors name the first offending row, counting data rows from 0.
+    fn infer_dtype(sl: &[String]) -> Result<Dtype, Box<dyn Error>> {
+        let options = options();
+        if !sl.is_empty() && sl.iter().all(|s| s.is_empty()) {
+            return Ok(Dtype::Float);
+        }
+        let mut inferred = None;
+        for (row, s) in sl.iter().enumerate().filter(|(_, s)| !s.is_empty()) {
+            let entry = if *s == options.na_token {
+                Dtype::Float
+            } else {
+                Dtype::of_cell(s)
+                    .ok_or_else(|| format!("row {row}: could not infer dtype from {s:?}"))?
+            };
+            inferred = Some(match (entry, inferred) {
+                (entry, None) => entry,
+                (Dtype::Float, Some(Dtype::Int)) | (Dtype::Int, Some(Dtype::Float))
+                    if !options.strict_inference =>
+                {
+                    Dtype::Float
+                }
+                (entry, Some(dtype)) if entry == dtype => dtype,
+                (entry, Some(dtype)) => {
+                    return Err(format!(
+                    "row {row}: Incompatible mixture of dtypes inferred: {entry:?} and {dtype:?}"
+                )
+                    .into())
+                }
+            });
+        }
+        inferred.ok_or_else(|| "Empty sequence of entries provided for inference".into())
+    }
+    fn from_untyped(sl: &[String]) -> Result<Self, Box<dyn Error>> {
+        Self::from_typed(sl, Self::infer_dtype(sl)?)
+    }
+
+    /// Parses every cell as `dtype`, erroring on the first that doesn't conform. Empty cells
+    /// become nulls.
+    fn from_typed(sl: &[String], dtype: Dtype) -> Result<Self, Box<dyn Error>> {
+        let nonconforming = |item: &String| format!("{item:?} is not a valid {dtype:?}");
+        let present: Vec<String> = sl.iter().filter(|s| !s.is_empty()).cloned().collect();
+        let series = match dtype {
+            Dtype::Int => Series::Int(ConcreteInt {
+                items: collect_early_exit(present.iter(), |item| {
+                    item.parse::<i64>().map_err(|_| nonconforming(item))
+                })?
+                .into(),
+            }),
+            Dtype::Float => {
+                let na_token = options().na_token;
+                Series::Float(ConcreteFloat {
+                    items: collect_early_exit(present.iter(), |item| {
+                        if *item == na_token {
+                            Ok(f64::NAN)
+                        } else {
+                            item.parse::<f64>().map_err(|_| nonconforming(item))
+                        }
+                    })?
+                    .into(),
+                })
+            }
+            Dtype::String => Series::String(ConcreteString {
+                items: collect_early_exit(present.iter(), |item| match Dtype::infer(item) {
+                    Some(Dtype::String) => Ok(unquote(item)),
+                    None if Dtype::of_cell(item).is_some() => Ok(item.clone()),
+                    _ => Err(nonconforming(item)),
+                })?
+                .into(),
+            }),
+            Dtype::Bool => Series::Bool(ConcreteBool {
+                items: collect_early_exit(present.iter(), |item| {
+                    item.parse::<bool>().map_err(|_| nonconforming(item))
+                })?
+                .into(),
+            }),
+        };
+        if present.len() == sl.len() {
+            return Ok(series);
+        }
+        // Spread the parsed values back out, with a placeholder in each null slot.
+        let mut next = 0;
+        let positions: Vec<Option<usize>> = sl
+            .iter()
+            .map(|s| {
+                (!s.is_empty()).then(|| {
+                    next += 1;
+                    next - 1
+                })
+            })
+            .collect();
+        Ok(series.take_or_null(&positions))
+    }
+    fn modes(&self) -> Self {
+        if self.validity().is_some() {
+            return self.drop_nulls().modes();
+        }
+        match self {
+            Self::Int(ConcreteInt { items }) => {
+                let mut items = most_frequent(items, |v| *v);
+                items.sort();
+                Self::Int(ConcreteInt {
+                    items: items.into(),
+                })
+            }
+            Self::Float(ConcreteFloat { items }) => {
+                let mut items = most_frequent(items, |v| v.to_bits());
+                items.sort_by(f64::total_cmp);
+                Self::Float(ConcreteFloat {
+                    items: items.into(),
+                })
+            }
+            Self::String(ConcreteString { items }) => {
+                let mut items = most_frequent(items, |v| v.clone());
+                items.sort();
+                Self::String(ConcreteString {
+                    items: items.into(),
+                })
+            }
+            Self::Bool(ConcreteBool { items }) => {
+                let mut items = most_frequent(items, |v| *v);
+                items.sort();
+                Self::Bool(ConcreteBool {
+                    items: items.into(),
+                })
+            }
+        }
+    }
+
+    /// Sum over each trailing window of `window` elements as a Float series, with NaN where
+    /// the window is incomplete and null where it holds a null. `None` for non-numeric series.
+    fn rolling_sum(&self, window: usize) -> Option<Self> {
+        let values: Vec<f64> = match self {
+            Self::Int(ConcreteInt { items }) => items.iter().map(|v| *v as f64).collect(),
+            Self::Float(ConcreteFloat { items }) => items.to_vec(),
+            Self::String(_) | Self::Bool(_) => return None,
+        };
+        let items = (0..values.len())
+            .map(|i| {
+                if i + 1 < window {
+                    f64::NAN
+                } else {
+                    values[i + 1 - window..=i].iter().sum()
+                }
+            })
+            .collect();
+        let validity = self.validity().map(|v| {
+            let mut nulls_in_window = 0;
+            let validity = (0..values.len()).map(|i| {
+                nulls_in_window += usize::from(v.get(i) != Some(true));
+                if i >= window {
+                    nulls_in_window -= usize::from(v.get(i - window) != Some(true));
+                }
+                nulls_in_window == 0
+            });
+            Arc::new(validity.collect())
+        });
+        Some(Self::Float(ConcreteFloat { items }).with_validity(validity))
+    }
+
+    /// Running fold of the elements from the first up to each position. Nulls stay null and
+    /// are left out of the fold.
+    fn cumulative(
+        &self,
+        int_op: impl Fn(i64, i64) -> PyResult<i64>,
+        float_op: impl Fn(f64, f64) -> f64,
+    ) -> PyResult<Self> {
+        let valid = |i: usize| self.is_valid(i);
+        let folded = match self {
+            Self::Int(ConcreteInt { items }) => {
+                let mut acc: Option<i64> = None;
+                let items = items
+                    .iter()
+                    .enumerate()
+                    .map(|(i, x)| {
+                        if !valid(i) {
+                            return Ok(*x);
+                        }
+                        let next = acc.map_or(Ok(*x), |a| int_op(a, *x))?;
+                        acc = Some(next);
+                        Ok(next)
+                    })
+                    .collect::<PyResult<_>>()?;
+                Self::Int(ConcreteInt { items })
+            }
+            Self::Float(ConcreteFloat { items }) => {
+                let items = items
+                    .iter()
+                    .enumerate()
+                    .scan(None, |acc: &mut Option<f64>, (i, x)| {
+                        if !valid(i) {
+                            return Some(*x);
+                        }
+                        let next = acc.map_or(*x, |a| float_op(a, *x));
+                        *acc = Some(next);
+                        Some(next)
+                    })
+                    .collect();
+                Self::Float(ConcreteFloat { items })
+            }
+            Self::String(_) | Self::Bool(_) => {
+                return Err(PyValueError::new_err(
+                    "Cumulative operations are undefined for non-numeric Series",
+                ))
+            }
+        };
+        Ok(folded.with_validity(self.validity().cloned()))
+    }
+
+    /// `first_occurrences` of the elements, nulls counting as one more distinct value.
+    fn occurrences(&self) -> Vec<(usize, usize)> {
+        let valid = |i: usize| self.is_valid(i);
+        match self {
+            Self::Int(ConcreteInt { items }) => {
+                first_occurrences(items, |i, v| valid(i).then_some(*v))
+            }
+            Self::Float(ConcreteFloat { items }) => {
+                first_occurrences(items, |i, v| valid(i).then_some(v.to_bits()))
+            }
+            Self::String(ConcreteString { items }) => {
+                first_occurrences(items, |i, v| valid(i).then_some(v))
+            }
+            Self::Bool(ConcreteBool { items }) => {
+                first_occurrences(items, |i, v| valid(i).then_some(*v))
+            }
+        }
+    }
+
+    /// `f` of the elements of a String series, keeping its nulls; other dtypes raise, naming
+    /// `method`.
+    fn map_strings(&self, method: &str, f: impl FnOnce(&[String]) -> Self) -> PyResult<Self> {
+        match self {
+            Self::String(ConcreteString { items }) => {
+                Ok(f(items).with_validity(self.validity().cloned()))
+            }
+            _ => Err(PyValueError::new_err(format!(
+                "{method} is undefined for {:?} Series",
+                self.dtype()
+            ))),
+        }
+    }
+
+    /// Pickle state: the dtype name, every element (null placeholders included) and the
+    /// validity mask, or None when nothing is null.
+    fn state(&self, py: Python<'_>) -> PyObject {
+        let items = match self {
+            Self::Int(ConcreteInt { items }) => items[..].to_object(py),
+            Self::Float(ConcreteFloat { items }) => items[..].to_object(py),
+            Self::String(ConcreteString { items }) => items[..].to_object(py),
+            Self::Bool(ConcreteBool { items }) => items[..].to_object(py),
+        };
+        let validity = self.validity().map(|v| v.to_vec());
+        (format!("{:?}", self.dtype()), items, validity).to_object(py)
+    }
+
+    /// Inverse of `state`.
+    fn from_state(state: &Bound<'_, PyAny>) -> PyResult<Self> {
+        let (dtype, items, validity): (String, Bound<'_, PyAny>, Option<Vec<bool>>) =
+            state.extract()?;
+        let series = match Dtype::parse(&dtype)? {
+            Dtype::Int => Self::Int(ConcreteInt {
+                items: items.extract::<Vec<i64>>()?.into(),
+            }),
+            Dtype::Float => Self::Float(ConcreteFloat {
+                items: items.extract::<Vec<f64>>()?.into(),
+            }),
+            Dtype::String => Self::String(ConcreteString {
+                items: items.extract::<Vec<String>>()?.into(),
+            }),
+            Dtype::Bool => Self::Bool(ConcreteBool {
+                items: items.extract::<Vec<bool>>()?.into(),
+            }),
+        };
+        if validity.as_ref().is_some_and(|v| v.len() != series.len()) {
+            return Err(PyValueError::new_err(
+                "Validity mask length does not match the Series",
+            ));
+        }
+        Ok(series.with_validity(validity.map(|v| Arc::new(v.into_iter().collect()))))
+    }
+
+    /// `int` or `float` of each element of a numeric series, keeping its nulls. `int` returns
+    /// None on overflow, which raises; other dtypes raise, naming `method`.
+    fn numeric_map(
+        &self,
+        method: &str,
+        int: impl Fn(i64) -> Option<i64>,
+        float: impl Fn(f64) -> f64,
+    ) -> PyResult<Self> {
+        let mapped = match self {
+            Self::Int(ConcreteInt { items }) => Self::Int(ConcreteInt {
+                items: items
+                    .iter()
+                    .enumerate()
+                    .map(|(i, x)| match int(*x) {
+                        Some(x) => Ok(x),
+                        // Null slots hold placeholders that needn't convert.
+                        None if !self.is_valid(i) => Ok(0),
+                        None => Err(PyOverflowError::new_err(format!(
+                            "{method}({x}) overflows Int at index {i}"
+                        ))),
+                    })
+                    .collect::<PyResult<_>>()?,
+            }),
+            Self::Float(ConcreteFloat { items }) => Self::Float(ConcreteFloat {
+                items: items.iter().map(|x| float(*x)).collect(),
+            }),
+            _ => {
+                return Err(PyValueError::new_err(format!(
+                    "{method} is undefined for {:?} Series",
+                    self.dtype()
+                )))
+            }
+        };
+        Ok(mapped.with_validity(self.validity().cloned()))
+    }
+
+    /// Whether `other` has the same dtype, nulls and other elements, comparing floats bitwise.
+    fn equals(&self, other: &Series) -> bool {
+        fn same<T>(a: &Buffer<T>, b: &Buffer<T>, eq: impl Fn(&T, &T) -> bool) -> bool {
+            a.len() == b.len()
+                && a.validity == b.validity
+                && (0..a.len()).all(|i| !a.is_valid(i) || eq(&a[i], &b[i]))
+        }
+        match (self, other) {
+            (Self::Int(ConcreteInt { items: a }), Self::Int(ConcreteInt { items: b })) => {
+                same(a, b, PartialEq::eq)
+            }
+            (Self::Float(ConcreteFloat { items: a }), Self::Float(ConcreteFloat { items: b })) => {
+                same(a, b, |x, y| x.to_bits() == y.to_bits())
+            }
+            (
+                Self::String(ConcreteString { items: a }),
+                Self::String(ConcreteString { items: b }),
+            ) => same(a, b, PartialEq::eq),
+            (Self::Bool(ConcreteBool { items: a }), Self::Bool(ConcreteBool { items: b })) => {
+                same(a, b, PartialEq::eq)
+            }
+            _ => false,
+        }
+    }
+
+    /// Gathers the elements at `indices`, in that order.
+    fn take(&self, indices: &[usize]) -> Self {
+        let taken = match self {
+            Self::Int(ConcreteInt { items }) => Self::Int(ConcreteInt {
+                items: indices.iter().map(|i| items[*i]).collect(),
+            }),
+            Self::Float(ConcreteFloat { items }) => Self::Float(ConcreteFloat {
+                items: indices.iter().map(|i| items[*i]).collect(),
+            }),
+            Self::String(ConcreteString { items }) => Self::String(ConcreteString {
+                items: indices.iter().map(|i| items[*i].clone()).collect(),
+            }),
+            Self::Bool(ConcreteBool { items }) => Self::Bool(ConcreteBool {
+                items: indices.iter().map(|i| items[*i]).collect(),
+            }),
+        };
+        let validity = self
+            .validity()
+            .map(|v| Arc::new(indices.iter().map(|i| v.get(*i) == Some(true)).collect()));
+        taken.with_validity(validity)
+    }
+
+    /// The valid elements in order, with every null slot dropped.
+    fn drop_nulls(&self) -> Self {
+        if self.validity().is_none() {
+            return self.clone();
+        }
+        let valid: Vec<usize> = (0..self.len()).filter(|i| self.is_valid(*i)).collect();
+        self.take(&valid)
+    }
+
+    /// Like `take`, but `None` gives a null slot, holding NaN in Float series and the default
+    /// value otherwise.
+    fn take_or_null(&self, indices: &[Option<usize>]) -> Self {
+        let taken = match self {
+            Self::Int(ConcreteInt { items }) => Self::Int(ConcreteInt {
+                items: indices.iter().map(|i| i.map_or(0, |i| items[i])).collect(),
+            }),
+            Self::Float(ConcreteFloat { items }) => Self::Float(ConcreteFloat {
+                items: indices
+                    .iter()
+                    .map(|i| i.map_or(f64::NAN, |i| items[i]))
+                    .collect(),
+            }),
+            Self::String(ConcreteString { items }) => Self::String(ConcreteString {
+                items: indices
+                    .iter()
+                    .map(|i| i.map_or(String::new(), |i| items[i].clone()))
+                    .collect(),
+            }),
+            Self::Bool(ConcreteBool { items }) => Self::Bool(ConcreteBool {
+                items: indices
+                    .iter()
+                    .map(|i| i.is_some_and(|i| items[i]))
+                    .collect(),
+            }),
+        };
+        let validity = indices
+            .iter()
+            .map(|i| i.is_some_and(|i| self.is_valid(i)))
+            .collect();
+        taken.with_validity(Some(Arc::new(validity)))
+    }
+
+    /// Like `take`, but `None` marks a missing row, filled with NaN in numeric series (Int
+    /// promotes to Float), "" in String series and false in Bool series.
+    fn take_or_missing(&self, indices: &[Option<usize>]) -> Self {
+        if let Some(indices) = indices.iter().copied().collect::<Option<Vec<_>>>() {
+            return self.take(&indices);
+        }
+        match self {
+            Self::Int(ConcreteInt { items }) => Self::Float(ConcreteFloat {
+                items: indices
+                    .iter()
+                    .map(|i| i.map_or(f64::NAN, |i| items[i] as f64))
+                    .collect(),
+            }),
+            Self::Float(ConcreteFloat { items }) => Self::Float(ConcreteFloat {
+                items: indices
+                    .iter()
+                    .map(|i| i.map_or(f64::NAN, |i| items[i]))
+                    .collect(),
+            }),
+            Self::String(ConcreteString { items }) => Self::String(ConcreteString {
+                items: indices
+                    .iter()
+                    .map(|i| i.map_or(String::new(), |i| items[i].clone()))
+                    .collect(),
+            }),
+            Self::Bool(ConcreteBool { items }) => Self::Bool(ConcreteBool {
+                items: indices
+                    .iter()
+                    .map(|i| i.is_some_and(|i| items[i]))
+                    .collect(),
+            }),
+        }
+    }
+
+    /// Executor value sharing this series' buffer, for binding a column as an expression
+    /// variable without copying it. String series and nulls have no executor equivalent.
+    fn to_var(&self) -> PyResult<exec::Var> {
+        if self.validity().is_some() {
+            return Err(PyValueError::new_err(
+                "Series with nulls cannot be used in expressions",
+            ));
+        }
+        match self {
+            Self::Int(ConcreteInt { items }) => Ok(exec::Var::IntV(items.items.clone())),
+            Self::Float(ConcreteFloat { items }) => Ok(exec::Var::FloatV(items.items.clone())),
+            Self::Bool(ConcreteBool { items }) => Ok(exec::Var::BoolV(items.items.clone())),
+            Self::String(_) => Err(PyValueError::new_err(
+                "String Series cannot be used in expressions",
+            )),
+        }
+    }
+
+    /// Number of elements that aren't missing: neither null nor, in Float series, NaN.
+    fn non_null_count(&self) -> usize {
+        (0..self.len())
+            .filter(|i| match self {
+                Self::Float(ConcreteFloat { items }) => !items[*i].is_nan(),
+                _ => self.is_valid(*i),
+            })
+            .count()
+    }
+
+    /// Approximate bytes held by the elements, including String contents.
+    fn memory_usage(&self) -> usize {
+        match self {
+            Self::Int(ConcreteInt { items }) => std::mem::size_of_val(items.as_slice()),
+            Self::Float(ConcreteFloat { items }) => std::mem::size_of_val(items.as_slice()),
+            Self::Bool(ConcreteBool { items }) => std::mem::size_of_val(items.as_slice()),
+            Self::String(ConcreteString { items }) => {
+                std::mem::size_of_val(items.as_slice())
+                    + items.iter().map(String::len).sum::<usize>()
+            }
+        }
+    }
+
+    /// Element at `index` as a Python scalar, or `None` if it is null.
+    fn get(&self, py: Python<'_>, index: usize) -> PyObject {
+        if !self.is_valid(index) {
+            return py.None();
+        }
+        match self {
+            Self::Int(ConcreteInt { items }) => items[index].into_py(py),
+            Self::Float(ConcreteFloat { items }) => items[index].into_py(py),
+            Self::String(ConcreteString { items }) => items[index].clone().into_py(py),
+            Self::Bool(ConcreteBool { items }) => items[index].into_py(py),
+        }
+    }
+
+    fn validity(&self) -> Option<&Arc<exec::BitVec>> {
+        match self {
+            Self::Int(ConcreteInt { items }) => items.validity.as_ref(),
+            Self::Float(ConcreteFloat { items }) => items.validity.as_ref(),
+            Self::String(ConcreteString { items }) => items.validity.as_ref(),
+            Self::Bool(ConcreteBool { items }) => items.validity.as_ref(),
+        }
+    }
+
+    fn is_valid(&self, index: usize) -> bool {
+        self.validity().is_none_or(|v| v.get(index) == Some(true))
+    }
+
+    /// Element `index` as it appears in the repr.
+    fn element_repr(&self, index: usize) -> String {
+        if !self.is_valid(index) {
+            return format!("{Null:?}");
+        }
+        match self {
+            Self::Int(ConcreteInt { items }) => format!("{:?}", items[index]),
+            Self::Float(ConcreteFloat { items }) => format!("{:?}", items[index]),
+            Self::String(ConcreteString { items }) => format!("{:?}", items[index]),
+            Self::Bool(ConcreteBool { items }) => format!("{:?}", items[index]),
+        }
+    }
+
+    /// The same elements with `validity` as the null bitmap.
+    fn with_validity(self, validity: Option<Arc<exec::BitVec>>) -> Self {
+        match self {
+            Self::Int(ConcreteInt { items }) => Self::Int(ConcreteInt {
+                items: items.with_validity(validity),
+            }),
+            Self::Float(ConcreteFloat { items }) => Self::Float(ConcreteFloat {
+                items: items.with_validity(validity),
+            }),
+            Self::String(ConcreteString { items }) => Self::String(ConcreteString {
+                items: items.with_validity(validity),
+            }),
+            Self::Bool(ConcreteBool { items }) => Self::Bool(ConcreteBool {
+                items: items.with_validity(validity),
+            }),
+        }
+    }
+
+    /// Validity of an element-wise result of `self` and `other`: null wherever either is.
+    fn combined_validity(&self, other: &Operand) -> Option<Arc<exec::BitVec>> {
+        let rhs = match other {
+            Operand::Series(rhs) => rhs.validity(),
+            _ => None,
+        };
+        match (self.validity(), rhs) {
+            (None, None) => None,
+            (Some(v), None) | (None, Some(v)) => Some(v.clone()),
+            (Some(l), Some(r)) => Some(Arc::new(
+                l.iter().zip(r.iter()).map(|(a, b)| a && b).collect(),
+            )),
+        }
+    }
+
+    /// Applies an element-wise arithmetic `op`, propagating nulls from either operand.
+    fn arithmetic(
+        &self,
+        other: Bound<'_, PyAny>,
+        op: impl FnOnce(&Self, Operand) -> PyResult<Self>,
+    ) -> PyResult<Self> {
+        let other = Operand::extract(&other)?;
+        let validity = self.combined_validity(&other);
+        Ok(op(self, other)?.with_validity(validity))
+    }
+
+    fn dtype(&self) -> Dtype {
+        match self {
+            Self::Int(_) => Dtype::Int,
+            Self::Float(_) => Dtype::Float,
+            Self::String(_) => Dtype::String,
+            Self::Bool(_) => Dtype::Bool,
+        }
+    }
+
+    fn i64(self) -> Result<ConcreteInt, Box<dyn Error>> {
+        match self {
+            Self::Int(concrete) => Ok(concrete),
+            _ => Err("Not Int Series".into()),
+        }
+    }
+
+    fn f64(self) -> Result<ConcreteFloat, Box<dyn Error>> {
+        match self {
+            Self::Float(concrete) => Ok(concrete),
+            _ => Err("Not Float Series".into()),
+        }
+    }
+
+    fn string(self) -> Result<ConcreteString, Box<dyn Error>> {
+        match self {
+            Self::String(concrete) => Ok(concrete),
+            _ => Err("Not String Series".into()),
+        }
+    }
+
+    fn bool(self) -> Result<ConcreteBool, Box<dyn Error>> {
+        match self {
+            Self::Bool(concrete) => Ok(concrete),
+            _ => Err("Not Bool Series".into()),
+        }
+    }
+
+    fn promote(lhs: Self, rhs: Self) -> PyResult<(Self, Self, Dtype)> {
+        match (&lhs, &rhs) {
+            (Self::Int(l), Self::Float(_)) => Ok((
+                Self::Float(ConcreteFloat {
+                    items: l.items.iter().map(|v| *v as f64).collect(),
+                }),
+                rhs,
+                Dtype::Float,
+            )), // todo: implement Into for ConcreteArray
+            (Self::Float(_), Self::Int(r)) => Ok((
+                lhs,
+                Self::Float(ConcreteFloat {
+                    items: r.items.iter().map(|v| *v as f64).collect(),
+                }),
+                Dtype::Float,
+            )), // todo: implement Into for ConcreteArray
+            (x, y) if x.dtype() == y.dtype() => {
+                let dtype = lhs.dtype();
+                Ok((lhs, rhs, dtype))
+            }
+            _ => Err(PyValueError::new_err("PromotionError!")),
+        }
+    }
+}
+
+/// Right-hand side of an element-wise operation: an aligned Series or a scalar broadcast across it.
+enum Operand {
+    Series(Series),
+    Int(i64),
+    Float(f64),
+    String(String),
+}
+
+impl Operand {
+    fn extract(other: &Bound<'_, PyAny>) -> PyResult<Self> {
+        if let Ok(series) = other.extract::<Series>() {
+            Ok(Self::Series(series))
+        } else if let Ok(value) = other.extract::<i64>() {
+            Ok(Self::Int(value))
+        } else if let Ok(value) = other.extract::<f64>() {
+            Ok(Self::Float(value))
+        } else if let Ok(value) = other.extract::<String>() {
+            Ok(Self::String(value))
+        } else {
+            Err(PyValueError::new_err(
+                "Operand must be a Series or a numeric or string scalar",
+            ))
+        }
+    }
+}
+
+/// Python's modulo, where the result takes the sign of the divisor.
+fn python_mod<T>(x: T, y: T) -> T
+where
+    T: Copy + PartialOrd + Default + std::ops::Rem<Output = T> + Add<Output = T>,
+{
+    let zero = T::default();
+    let r = x % y;
+    if r != zero && ((r < zero) != (y < zero)) {
+        r + y
+    } else {
+        r
+    }
+}
+
+impl Series {
+    /// Applies a numeric operation element-wise, promoting Int to Float when either side is Float.
+    /// `int_op` also receives the element's index so failures can point at it. A null in either
+    /// operand gives a null, and `int_op` never sees its placeholder.
+    fn numeric_op(
+        &self,
+        other: Operand,
+        int_op: impl Fn(usize, i64, i64) -> PyResult<i64>,
+        float_op: impl Fn(f64, f64) -> f64,
+    ) -> PyResult<Self> {
+        let validity = self.combined_validity(&other);
+        let valid = |i: usize| validity.as_ref().is_none_or(|v| v.get(i) == Some(true));
+        let int_op = |i, x, y| if valid(i) { int_op(i, x, y) } else { Ok(x) };
+        let result = self.dense_numeric_op(other, int_op, float_op)?;
+        Ok(result.with_validity(validity))
+    }
+
+    /// `numeric_op` on every slot, nulls included.
+    fn dense_numeric_op(
+        &self,
+        other: Operand,
+        int_op: impl Fn(usize, i64, i64) -> PyResult<i64>,
+        float_op: impl Fn(f64, f64) -> f64,
+    ) -> PyResult<Self> {
+        let rhs = match other {
+            Operand::Series(rhs) => rhs,
+            Operand::Int(y) => {
+                return match self {
+                    Self::Int(ConcreteInt { items }) => Ok(Self::Int(ConcreteInt {
+                        items: items
+                            .iter()
+                            .enumerate()
+                            .map(|(i, x)| int_op(i, *x, y))
+                            .collect::<PyResult<_>>()?,
+                    })),
+                    Self::Float(ConcreteFloat { items }) => Ok(Self::Float(ConcreteFloat {
+                        items: items.iter().map(|x| float_op(*x, y as f64)).collect(),
+                    })),
+                    Self::String(_) | Self::Bool(_) => Err(PyValueError::new_err(
+                        "Arithmetic is undefined for non-numeric Series",
+                    )),
+                };
+            }
+            Operand::Float(y) => {
+                return match self {
+                    Self::Int(ConcreteInt { items }) => Ok(Self::Float(ConcreteFloat {
+                        items: items.iter().map(|x| float_op(*x as f64, y)).collect(),
+                    })),
+                    Self::Float(ConcreteFloat { items }) => Ok(Self::Float(ConcreteFloat {
+                        items: items.iter().map(|x| float_op(*x, y)).collect(),
+                    })),
+                    Self::String(_) | Self::Bool(_) => Err(PyValueError::new_err(
+                        "Arithmetic is undefined for non-numeric Series",
+                    )),
+                };
+            }
+            Operand::String(_) => {
+                return Err(PyValueError::new_err(
+                    "Arithmetic is undefined for String operands",
+                ))
+            }
+        };
+        if self.len() != rhs.len() {
+            return Err(PyValueError::new_err(format!(
+                "Length mismatch: {} and {}",
+                self.len(),
+                rhs.len()
+            )));
+        }
+        let (lhs, rhs, dtype) = Self::promote(self.clone(), rhs)?;
+        match dtype {
+            Dtype::Int => {
+                let (lhs, rhs) = (lhs.i64().unwrap(), rhs.i64().unwrap());
+                Ok(Self::Int(ConcreteInt {
+                    items: lhs
+                        .items
+                        .iter()
+                        .zip(&rhs.items)
+                        .enumerate()
+                        .map(|(i, (x, y))| int_op(i, *x, *y))
+                        .collect::<PyResult<_>>()?,
+                }))
+            }
+            Dtype::Float => {
+                let (lhs, rhs) = (lhs.f64().unwrap(), rhs.f64().unwrap());
+                Ok(Self::Float(ConcreteFloat {
+                    items: lhs
+                        .items
+                        .iter()
+                        .zip(&rhs.items)
+                        .map(|(x, y)| float_op(*x, *y))
+                        .collect(),
+                }))
+            }
+            Dtype::String | Dtype::Bool => Err(PyValueError::new_err(
+                "Arithmetic is undefined for non-numeric Series",
+            )),
+        }
+    }
+
+    fn pow_op(&self, other: Operand) -> PyResult<Self> {
+        self.numeric_op(
+            other,
+            |_, x, y| {
+                let exponent = u32::try_from(y).map_err(|_| {
+                    PyValueError::new_err("Int Series can only be raised to non-negative powers")
+                })?;
+                x.checked_pow(exponent)
+                    .ok_or_else(|| PyOverflowError::new_err(format!("{x} ** {y} overflows Int")))
+            },
+            f64::powf,
+        )
+    }
+
+    /// Bounds each element to `[lower, upper]`; bounds may be scalars or aligned Series.
+    /// NaN elements are left as NaN.
+    fn clip_op(&self, lower: Option<Operand>, upper: Option<Operand>) -> PyResult<Self> {
+        let mut clipped = self.clone();
+        if let Some(lower) = lower {
+            clipped = clipped.numeric_op(
+                lower,
+                |_, x, y| Ok(x.max(y)),
+                |x, y| if x < y { y } else { x },
+            )?;
+        }
+        if let Some(upper) = upper {
+            clipped = clipped.numeric_op(
+                upper,
+                |_, x, y| Ok(x.min(y)),
+                |x, y| if x > y { y } else { x },
+            )?;
+        }
+        Ok(clipped)
+    }
+
+    /// Index at which `value` would be inserted to keep an already-sorted numeric series
+    /// sorted; `right` places it after any equal elements instead of before.
+    fn insertion_point(&self, value: &Operand, right: bool) -> PyResult<usize> {
+        fn point<T: PartialOrd>(items: &[T], value: &T, right: bool) -> usize {
+            if right {
+                items.partition_point(|x| x <= value)
+            } else {
+                items.partition_point(|x| x < value)
+            }
+        }
+        match (self, value) {
+            (Self::Int(ConcreteInt { items }), Operand::Int(v)) => Ok(point(items, v, right)),
+            (Self::Int(ConcreteInt { items }), Operand::Float(v)) => {
+                let items: Vec<f64> = items.iter().map(|x| *x as f64).collect();
+                Ok(point(&items, v, right))
+            }
+            (Self::Float(ConcreteFloat { items }), Operand::Int(v)) => {
+                Ok(point(items, &(*v as f64), right))
+            }
+            (Self::Float(ConcreteFloat { items }), Operand::Float(v)) => Ok(point(items, v, right)),
+            _ => Err(PyValueError::new_err(
+                "searchsorted requires a numeric series and numeric values",
+            )),
+        }
+    }
+
+    /// Bool mask of where each element's ordering against `other` satisfies `accept`. Numeric
+    /// series compare numerically, promoting Int to Float; String series compare
+    /// lexicographically. NaN is unordered, so it never satisfies `accept`. A null in either
+    /// operand gives a null, holding false so that filtering drops it.
+    fn compare(&self, other: Operand, accept: impl Fn(Ordering) -> bool) -> PyResult<Self> {
+        fn mask<T: PartialOrd>(
+            lhs: &[T],
+            rhs: &[T],
+            valid: impl Fn(usize) -> bool,
+            accept: impl Fn(Ordering) -> bool,
+        ) -> Vec<bool> {
+            // A single rhs element is a scalar broadcast across lhs.
+            lhs.iter()
+                .enumerate()
+                .map(|(i, x)| {
+                    let y = if rhs.len() == 1 { &rhs[0] } else { &rhs[i] };
+                    valid(i) && x.partial_cmp(y).is_some_and(&accept)
+                })
+                .collect()
+        }
+        let validity = self.combined_validity(&other);
+        let valid = |i: usize| validity.as_ref().is_none_or(|v| v.get(i) == Some(true));
+        let rhs = match other {
+            Operand::Series(rhs) => {
+                if self.len() != rhs.len() {
+                    return Err(PyValueError::new_err(format!(
+                        "Length mismatch: {} and {}",
+                        self.len(),
+                        rhs.len()
+                    )));
+                }
+                rhs
+            }
+            Operand::Int(y) => Self::Int(ConcreteInt {
+                items: vec![y].into(),
+            }),
+            Operand::Float(y) => Self::Float(ConcreteFloat {
+                items: vec![y].into(),
+            }),
+            Operand::String(y) => Self::String(ConcreteString {
+                items: vec![y].into(),
+            }),
+        };
+        let items = match Self::promote(self.clone(), rhs)? {
+            (Self::Int(l), Self::Int(r), _) => mask(&l.items, &r.items, valid, accept),
+            (Self::Float(l), Self::Float(r), _) => mask(&l.items, &r.items, valid, accept),
+            (Self::String(l), Self::String(r), _) => mask(&l.items, &r.items, valid, accept),
+            (Self::Bool(l), Self::Bool(r), _) => mask(&l.items, &r.items, valid, accept),
+            _ => unreachable!("promote yields matching dtypes"),
+        };
+        Ok(Self::Bool(ConcreteBool {
+            items: items.into(),
+        })
+        .with_validity(validity))
+    }
+
+    /// Element-wise comparison as a Bool Series. String series only support equality, and
+    /// NaN is unequal to everything, itself included.
+    fn compare_op(&self, other: Operand, op: CompareOp) -> PyResult<Self> {
+        let ordering = !matches!(op, CompareOp::Eq | CompareOp::Ne);
+        if ordering
+            && (matches!(self, Self::String(_))
+                || matches!(other, Operand::String(_) | Operand::Series(Self::String(_))))
+        {
+            return Err(PyValueError::new_err(
+                "String Series only support == and !=",
+            ));
+        }
+        if matches!(op, CompareOp::Ne) {
+            let equal = self.compare(other, Ordering::is_eq)?.bool().unwrap();
+            let items = &equal.items;
+            return Ok(Self::Bool(ConcreteBool {
+                items: (0..items.len())
+                    .map(|i| !items[i] && items.is_valid(i))
+                    .collect(),
+            })
+            .with_validity(items.validity.clone()));
+        }
+        self.compare(other, |o| op.matches(o))
+    }
+
+    fn between_mask(&self, low: Operand, high: Operand, left: bool, right: bool) -> PyResult<Self> {
+        let above = self.compare(low, |o| o.is_gt() || (left && o.is_eq()))?;
+        let below = self.compare(high, |o| o.is_lt() || (right && o.is_eq()))?;
+        let validity = above.combined_validity(&Operand::Series(below.clone()));
+        let (above, below) = (above.bool().unwrap(), below.bool().unwrap());
+        Ok(Self::Bool(ConcreteBool {
+            items: above
+                .items
+                .iter()
+                .zip(below.items.iter())
+                .map(|(a, b)| *a && *b)
+                .collect(),
+        })
+        .with_validity(validity))
+    }
+
+    /// Appends `value` in place. It must match the dtype, except that an int may be pushed
+    /// onto a Float series.
+    fn push(&mut self, value: &Bound<'_, PyAny>) -> PyResult<()> {
+        let dtype = self.dtype();
+        let mismatch = || {
+            PyValueError::new_err(format!(
+                "Cannot push {} onto a {dtype:?} Series",
+                value.get_type()
+            ))
+        };
+        let is_bool = value.is_instance_of::<PyBool>();
+        match self {
+            Self::Int(concrete) if !is_bool => {
+                concrete.push(value.extract().map_err(|_| mismatch())?)
+            }
+            Self::Float(concrete) if !is_bool => {
+                concrete.push(value.extract().map_err(|_| mismatch())?)
+            }
+            Self::String(concrete) => concrete.push(value.extract().map_err(|_| mismatch())?),
+            Self::Bool(concrete) => concrete.push(value.extract().map_err(|_| mismatch())?),
+            _ => return Err(mismatch()),
+        }
+        Ok(())
+    }
+
+    /// Position of the first smallest or largest element, skipping nulls and NaN.
+    fn arg_extreme(&self, wanted: Ordering) -> PyResult<usize> {
+        if self.validity().is_some() {
+            let valid: Vec<usize> = (0..self.len()).filter(|i| self.is_valid(*i)).collect();
+            return Ok(valid[self.take(&valid).arg_extreme(wanted)?]);
+        }
+        let index = match self {
+            Self::Int(ConcreteInt { items }) => first_extreme(items, wanted),
+            Self::Float(ConcreteFloat { items }) => first_extreme(items, wanted),
+            Self::String(ConcreteString { items }) => first_extreme(items, wanted),
+            Self::Bool(_) => {
+                return Err(PyValueError::new_err(
+                    "argmin and argmax require a numeric or String series",
+                ))
+            }
+        };
+        index.ok_or_else(|| PyValueError::new_err("Series has no comparable elements"))
+    }
+
+    /// Orders elements `i` and `j` ascending or descending, with NaN after every other Float
+    /// either way.
+    fn cmp_rows(&self, i: usize, j: usize, ascending: bool) -> Ordering {
+        let order = match self {
+            Self::Int(ConcreteInt { items }) => items[i].cmp(&items[j]),
+            Self::Float(ConcreteFloat { items }) => {
+                let (x, y) = (items[i], items[j]);
+                match (x.is_nan(), y.is_nan()) {
+                    (false, false) => x.partial_cmp(&y).unwrap(),
+                    (x_nan, y_nan) => return x_nan.cmp(&y_nan),
+                }
+            }
+            Self::String(ConcreteString { items }) => items[i].cmp(&items[j]),
+            Self::Bool(ConcreteBool { items }) => items[i].cmp(&items[j]),
+        };
+        if ascending {
+            order
+        } else {
+            order.reverse()
+        }
+    }
+
+    /// Positions of the elements in sorted order, with nulls after everything else.
+    fn sorted_positions(&self, ascending: bool) -> Vec<usize> {
+        let mut positions: Vec<usize> = (0..self.__len__()).collect();
+        positions.sort_by(|&i, &j| match (self.is_valid(i), self.is_valid(j)) {
+            (true, true) => self.cmp_rows(i, j, ascending),
+            (i_valid, j_valid) => j_valid.cmp(&i_valid),
+        });
+        positions
+    }
+
+    /// Reduces a series to a single Python scalar, skipping nulls.
+    fn reduce(&self, py: Python<'_>, how: Aggregation) -> PyResult<PyObject> {
+        if self.validity().is_some() {
+            return self.drop_nulls().reduce(py, how);
+        }
+        match (self, how) {
+            (Self::Bool(_), _) | (Self::String(_), Aggregation::Sum) => Err(PyValueError::new_err(
+                format!("Cannot {how:?} a {:?} series", self.dtype()),
+            )),
+            (Self::Int(ConcreteInt { items }), Aggregation::Sum) => {
+                let sum = items.iter().try_fold(0i64, |acc, x| acc.checked_add(*x));
+                let sum = sum.ok_or_else(|| PyOverflowError::new_err("Sum overflows Int"))?;
+                Ok(sum.into_py(py))
+            }
+            (Self::Float(ConcreteFloat { items }), Aggregation::Sum) => {
+                // `Sum for f64` starts from -0.0, so fold from 0.0 for an empty series.
+                Ok(items.iter().fold(0.0, |acc, x| acc + x).into_py(py))
+            }
+            (_, Aggregation::Min | Aggregation::Max) => {
+                if self.len() == 0 {
+                    return Err(PyValueError::new_err(format!("{how:?} of empty series")));
+                }
+                let wanted = if how == Aggregation::Min {
+                    Ordering::Less
+                } else {
+                    Ordering::Greater
+                };
+                match (self, self.arg_extreme(wanted)) {
+                    // Every element is NaN.
+                    (Self::Float(_), Err(_)) => Ok(f64::NAN.into_py(py)),
+                    (_, index) => Ok(self.get(py, index?)),
+                }
+            }
+            _ => Err(PyValueError::new_err(format!(
+                "{how:?} is not supported on a Series"
+            ))),
+        }
+    }
+
+    /// Numeric elements as f64, promoting Int.
+    fn f64_values(&self) -> PyResult<Vec<f64>> {
+        match self {
+            Self::Int(ConcreteInt { items }) => Ok(items.iter().map(|v| *v as f64).collect()),
+            Self::Float(ConcreteFloat { items }) => Ok(items.to_vec()),
+            Self::String(_) | Self::Bool(_) => {
+                Err(PyValueError::new_err("Expected a numeric series"))
+            }
+        }
+    }
+
+    /// Rescales to [0, 1]. A constant series has no range and scales to all zeros. Nulls stay
+    /// null and don't count towards the range.
+    fn minmax_scaled(&self) -> PyResult<Self> {
+        let present = self.drop_nulls().f64_values()?;
+        let min = present.iter().copied().fold(f64::INFINITY, f64::min);
+        let max = present.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+        let range = max - min;
+        let items = self
+            .f64_values()?
+            .iter()
+            .map(|v| if range == 0.0 { 0.0 } else { (v - min) / range })
+            .collect();
+        Ok(Self::Float(ConcreteFloat { items }).with_validity(self.validity().cloned()))
+    }
+
+    /// Standardizes to mean 0 and population standard deviation 1. A constant series has
+    /// zero deviation and standardizes to all zeros. Nulls stay null and don't count towards
+    /// the mean or deviation.
+    fn zscored(&self) -> PyResult<Self> {
+        let present = self.drop_nulls().f64_values()?;
+        let n = present.len() as f64;
+        let mean = present.iter().sum::<f64>() / n;
+        let std = (present.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / n).sqrt();
+        let items = self
+            .f64_values()?
+            .iter()
+            .map(|v| if std == 0.0 { 0.0 } else { (v - mean) / std })
+            .collect();
+        Ok(Self::Float(ConcreteFloat { items }).with_validity(self.validity().cloned()))
+    }
+
+    /// Subtracts element-wise; Int results that leave the i64 range raise an overflow error
+    /// instead of wrapping. Null slots are skipped, so their placeholders can't overflow.
+    fn sub_op(&self, other: Operand) -> PyResult<Self> {
+        self.numeric_op(other, checked_sub, |x, y| x - y)
+    }
+
+    /// Adds element-wise, or concatenates String series; a numeric scalar is broadcast. Int
+    /// sums that leave the i64 range raise an overflow error instead of wrapping.
+    fn add_op(&self, other: Operand) -> PyResult<Self> {
+        match other {
+            Operand::Series(rhs @ Self::String(_)) => self.clone().add(rhs),
+            numeric => self.numeric_op(numeric, checked_add, |x, y| x + y),
+        }
+    }
+
+    /// Multiplies element-wise; Int products that leave the i64 range raise an overflow error.
+    fn mul_op(&self, other: Operand) -> PyResult<Self> {
+        self.numeric_op(
+            other,
+            |i, x, y| {
+                x.checked_mul(y).ok_or_else(|| {
+                    PyOverflowError::new_err(format!("{x} * {y} overflows Int at index {i}"))
+                })
+            },
+            |x, y| x * y,
+        )
+    }
+
+    /// True division: the result is always Float, and dividing by zero gives inf or NaN.
+    fn div_op(&self, other: Operand) -> PyResult<Self> {
+        let lhs = Self::Float(ConcreteFloat {
+            items: self.f64_values()?.into(),
+        })
+        .with_validity(self.validity().cloned());
+        lhs.numeric_op(other, |_, _, _| unreachable!("lhs is Float"), |x, y| x / y)
+    }
+
+    fn mod_op(&self, other: Operand) -> PyResult<Self> {
+        self.numeric_op(
+            other,
+            |_, x, y| {
+                if y == 0 {
+                    Err(PyZeroDivisionError::new_err("Int modulo by zero"))
+                } else {
+                    Ok(python_mod(x, y))
+                }
+            },
+            python_mod,
+        )
+    }
+}
+
+impl Add for Series {
+    type Output = PyResult<Self>;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        if self.len() != rhs.len() {
+            return Err(PyValueError::new_err(format!(
+                "Length mismatch: {} and {}",
+                self.len(),
+                rhs.len()
+            )));
+        }
+        let (lhs, rhs, dtype) = Self::promote(self, rhs)?;
+        match dtype {
+            Dtype::Float => Ok(Self::Float(lhs.f64().unwrap() + rhs.f64().unwrap())),
+            Dtype::Int => Ok(Self::Int((lhs.i64().unwrap() + rhs.i64().unwrap())?)),
+            Dtype::String => Ok(Self::String(lhs.string().unwrap() + rhs.string().unwrap())),
+            Dtype::Bool => Err(PyValueError::new_err("Cannot add Bool Series")),
+        }
+    }
+}
+impl Sub for Series {
+    type Output = PyResult<Self>;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        if self.len() != rhs.len() {
+            return Err(PyValueError::new_err(format!(
+                "Length mismatch: {} and {}",
+                self.len(),
+                rhs.len()
+            )));
+        }
+        let (lhs, rhs, dtype) = Self::promote(self, rhs)?;
+        match dtype {
+            Dtype::Float => Ok(Self::Float(lhs.f64().unwrap() - rhs.f64().unwrap())),
+            Dtype::Int => Ok(Self::Int((lhs.i64().unwrap() - rhs.i64().unwrap())?)),
+            Dtype::String | Dtype::Bool => Err(PyValueError::new_err(format!(
+                "Cannot subtract {dtype:?} Series"
+            ))),
+        }
+    }
+}
+
+impl ConcreteArrayTrait for Series {
+    fn len(&self) -> usize {
+        match self {
+            Self::Int(concrete) => concrete.len(),
+            Self::Float(concrete) => concrete.len(),
+            Self::String(concrete) => concrete.len(),
+            Self::Bool(concrete) => concrete.len(),
+        }
+    }
+}
+
+// Pickle finds the class again by module and name.
+#[pyclass(module = "dfrs")]
+#[derive(Clone)]
+struct DataFrame {
+    /// Columns in insertion order.
+    item: IndexMap<String, Series>,
+}
+
+#[pymethods]
+impl DataFrame {
+    #[new]
+    fn create(pydict: Bound<'_, PyDict>) -> PyResult<Self> {
+        let mut item: IndexMap<String, Series> = IndexMap::new();
+        let mut length: Option<usize> = None;
+        for (k, v) in pydict {
+            let col_name: String = k.extract()?;
+            let vec: Series = Series::create(v)?;
+            if *length.get_or_insert(vec.len()) != vec.len() {
+                return Err(PyValueError::new_err(
+                    "Incompatible length columns provided",
+                ));
+            }
+            item.insert(col_name, vec);
+        }
+        Ok(Self { item })
+    }
+    fn __repr__(&self) -> String {
+        format!("DataFrame({:?})", self.item)
+    }
+
+    fn __str__(&self) -> String {
+        format!("DataFrame({:?})", self.item)
+    }
+
+    fn __len__(&self) -> usize {
+        self.item.values().next().map_or(0, |s| s.len())
+    }
+
+    /// Iterates over the rows, each a dict from column name to value.
+    fn __iter__(&self) -> Rows {
+        Rows {
+            frame: self.clone(),
+            index: 0,
+        }
+    }
+
+    /// Row and column counts.
+    fn shape(&self) -> (usize, usize) {
+        (self.__len__(), self.item.len())
+    }
+
+    /// Pickle state: each column's name with the state of its Series, in column order.
+    fn __getstate__(&self, py: Python<'_>) -> Vec<(String, PyObject)> {
+        self.item
+            .iter()
+            .map(|(name, series)| (name.clone(), series.state(py)))
+            .collect()
+    }
+
+    fn __setstate__(&mut self, state: Vec<(String, Bound<'_, PyAny>)>) -> PyResult<()> {
+        let item = state
+            .into_iter()
+            .map(|(name, state)| Ok((name, Series::from_state(&state)?)))
+            .collect::<PyResult<IndexMap<_, _>>>()?;
+        if item.values().any(|s| s.len() != item[0].len()) {
+            return Err(PyValueError::new_err(
+                "Incompatible length columns provided",
+            ));
+        }
+        self.item = item;
+        Ok(())
+    }
+
+    /// Pickle creates an empty frame before restoring its state.
+    fn __getnewargs__(&self, py: Python<'_>) -> (Py<PyDict>,) {
+        (PyDict::new_bound(py).unbind(),)
+    }
+
+    /// Whether both frames have the same columns, in any order, holding equal Series: the
+    /// same dtype, the same nulls and the same other elements. Floats compare bitwise, so NaN
+    /// equals NaN but 0.0 differs from -0.0.
+    fn __eq__(&self, other: &DataFrame) -> bool {
+        self.item.len() == other.item.len()
+            && self.item.iter().all(|(name, series)| {
+                other
+                    .item
+                    .get(name)
+                    .is_some_and(|other| series.equals(other))
+            })
+    }
+
+    /// Whether the frame has a column named `key`, so `in` tests columns rather than
+    /// iterating rows.
+    fn __contains__(&self, key: &str) -> bool {
+        self.item.contains_key(key)
+    }
+
+    /// Column names in insertion order, the order used by `to_records` and `to_csv`.
+    #[getter]
+    fn columns(&self) -> Vec<String> {
+        self.item.keys().cloned().collect()
+    }
+
+    /// The column named `index`, a frame of the columns a list `index` names, the row at an
+    /// integer `index` as a dict, or, when `index` is a Bool Series, the rows where it is true.
+    fn __getitem__(&self, py: Python<'_>, index: Bound<'_, PyAny>) -> PyResult<PyObject> {
+        if let Ok(position) = index.extract::<isize>() {
+            let position = resolve_index(position, self.__len__())?;
+            return Ok(self.row(py, position)?.into_any().unbind());
+        }
+        if let Ok(columns) = index.extract::<Vec<String>>() {
+            let item = collect_early_exit(columns.iter(), |column| {
+                let series = self
+                    .item
+                    .get(column)
+                    .ok_or_else(|| PyKeyError::new_err(format!("Unknown column {column:?}")))?;
+                Ok::<_, PyErr>((column.clone(), series.clone()))
+            })?;
+            return Ok(Self {
+                item: item.into_iter().collect(),
+            }
+            .into_py(py));
+        }
+        if let Ok(mask) = index.extract::<Series>() {
+            let Series::Bool(ConcreteBool { items: mask }) = mask else {
+                return Err(PyValueError::new_err(format!(
+                    "Cannot index a DataFrame with a {:?} Series; expected Bool",
+                    mask.dtype()
+                )));
+            };
+            if mask.len() != self.__len__() {
+                return Err(PyValueError::new_err(format!(
+                    "Mask has {} elements but the frame has {} rows",
+                    mask.len(),
+                    self.__len__()
+                )));
+            }
+            let rows: Vec<usize> = (0..mask.len()).filter(|i| mask[*i]).collect();
+            return Ok(self.take(&rows).into_py(py));
+        }
+        let key: String = index.extract()?;
+        // TODO: keep data in rust? Make Series PyClass compatible with print for viewing in python
+        let result = self
+            .item
+            .get(&key)
+            .ok_or(PyKeyError::new_err("Unrecognised key"))?;
+        Ok(result.clone().into_py(py))
+    }
+
+    fn __setitem__(&mut self, index: Bound<'_, PyString>, value: Bound<'_, PyAny>) -> PyResult<()> {
+        let key: String = index.extract()?;
+        let value: Series = Series::create(value)?;
+        let current_length = self.__len__();
+        if current_length != 0 && (current_length != value.len()) {
+            return Err(PyValueError::new_err(
+                "Incompatible length series inserted!",
+            ));
+        }
+        self.item.insert(key, value);
+        Ok(())
+    }
+
+    /// Copy of the frame with column `name` added, or replaced where it is, built from a
+    /// Series or a list as in the constructor. Unlike item assignment this leaves the frame
+    /// untouched, so calls can be chained.
+    fn assign(&self, name: String, values: Bound<'_, PyAny>) -> PyResult<Self> {
+        let values = match values.extract::<Series>() {
+            Ok(series) => series,
+            Err(_) => Series::create(values)?,
+        };
+        if !self.item.is_empty() && values.len() != self.__len__() {
+            return Err(PyValueError::new_err(format!(
+                "Column {name:?} has {} elements but the frame has {} rows",
+                values.len(),
+                self.__len__()
+            )));
+        }
+        let mut frame = self.clone();
+        frame.item.insert(name, values);
+        Ok(frame)
+    }
+
+    /// Renames columns in place by an old name to new name `mapping`, keeping their positions.
+    fn rename(&mut self, mapping: Bound<'_, PyDict>) -> PyResult<()> {
+        let mapping: HashMap<String, String> = mapping.extract()?;
+        if let Some(old) = mapping.keys().find(|old| !self.item.contains_key(*old)) {
+            return Err(PyKeyError::new_err(format!("Unknown column {old:?}")));
+        }
+        let mut targets = HashSet::new();
+        for old in self.item.keys() {
+            let new = mapping.get(old).unwrap_or(old);
+            if !targets.insert(new) {
+                return Err(PyValueError::new_err(format!(
+                    "Renaming would produce more than one column {new:?}"
+                )));
+            }
+        }
+        self.item = std::mem::take(&mut self.item)
+            .into_iter()
+            .map(|(old, series)| (mapping.get(&old).cloned().unwrap_or(old), series))
+            .collect();
+        Ok(())
+    }
+
+    /// The first `n` rows, or every row when there are fewer.
+    #[pyo3(signature = (n = 5))]
+    fn head(&self, n: usize) -> Self {
+        let rows: Vec<usize> = (0..n.min(self.__len__())).collect();
+        self.take(&rows)
+    }
+
+    /// The last `n` rows, or every row when there are fewer.
+    #[pyo3(signature = (n = 5))]
+    fn tail(&self, n: usize) -> Self {
+        let len = self.__len__();
+        let rows: Vec<usize> = (len - n.min(len)..len).collect();
+        self.take(&rows)
+    }
+
+    /// Copy of the frame without `columns`. Missing columns raise a KeyError unless `errors`
+    /// is "ignore".
+    #[pyo3(signature = (columns, errors = "raise"))]
+    fn drop(&self, columns: Vec<String>, errors: &str) -> PyResult<Self> {
+        let ignore = match errors {
+            "raise" => false,
+            "ignore" => true,
+            _ => {
+                return Err(PyValueError::new_err(format!(
+                    "Unknown errors policy {errors:?}; expected \"raise\" or \"ignore\""
+                )))
+            }
+        };
+        if let Some(missing) = columns.iter().find(|c| !self.item.contains_key(*c)) {
+            if !ignore {
+                return Err(PyKeyError::new_err(format!("Unknown column {missing:?}")));
+            }
+        }
+        let item = self
+            .item
+            .iter()
+            .filter(|(k, _)| !columns.contains(k))
+            .map(|(k, v)| (k.clone(), v.clone()))
+            .collect();
+        Ok(Self { item })
+    }
+
+    /// Copy of the frame with a column `name` built by calling `func` on each row as a dict.
+    fn apply_rows(&self, name: String, func: Bound<'_, PyAny>) -> PyResult<Self> {
+        let py = func.py();
+        let values = (0..self.__len__())
+            .map(|index| func.call1((self.row(py, index)?,)))
+            .collect::<PyResult<Vec<_>>>()?;
+        let column = Series::create(PyList::new_bound(py, values).into_any())?;
+        let mut frame = self.clone();
+        frame.item.insert(name, column);
+        Ok(frame)
+    }
+
+    /// Column name to a list of the column's values, in column order; the inverse of the
+    /// constructor.
+    fn to_dict(&self, py: Python<'_>) -> PyResult<Py<PyDict>> {
+        let dict = PyDict::new_bound(py);
+        for (name, series) in &self.item {
+            dict.set_item(name, series.to_list(py))?;
+        }
+        Ok(dict.unbind())
+    }
+
+    /// Each row as a tuple of scalars, ordered like `columns`.
+    fn to_records(&self, py: Python<'_>) -> Vec<Py<PyTuple>> {
+        let columns = self.columns();
+        (0..self.__len__())
+            .map(|index| {
+                PyTuple::new_bound(py, columns.iter().map(|c| self.item[c].get(py, index))).unbind()
+            })
+            .collect()
+    }
+
+    /// Stacks `frames` vertically. Every frame must have the same columns, which are ordered
+    /// as in the first; each column is joined as by `Series.concat`.
+    #[staticmethod]
+    fn concat(frames: Vec<DataFrame>) -> PyResult<Self> {
+        let first = frames
+            .first()
+            .ok_or_else(|| PyValueError::new_err("Cannot concat an empty list of DataFrames"))?;
+        for (i, frame) in frames.iter().enumerate().skip(1) {
+            let missing: Vec<&String> = first
+                .item
+                .keys()
+                .filter(|k| !frame.item.contains_key(*k))
+                .collect();
+            let extra: Vec<&String> = frame
+                .item
+                .keys()
+                .filter(|k| !first.item.contains_key(*k))
+                .collect();
+            if !missing.is_empty() || !extra.is_empty() {
+                return Err(PyValueError::new_err(format!(
+                    "DataFrame {i} is missing columns {missing:?} and has extra columns {extra:?}"
+                )));
+            }
+        }
+        let item = first
+            .item
+            .keys()
+            .map(|name| {
+                let parts = frames.iter().map(|f| f.item[name].clone()).collect();
+                let series = Series::concat(parts)
+                    .map_err(|e| PyValueError::new_err(format!("In column {name:?}: {e}")))?;
+                Ok((name.clone(), series))
+            })
+            .collect::<PyResult<_>>()?;
+        Ok(Self { item })
+    }
+
+    /// Builds a frame from row sequences, the `i`th element of each row going to `columns[i]`.
+    #[staticmethod]
+    fn from_records(
+        py: Python<'_>,
+        records: Vec<Bound<'_, PyAny>>,
+        columns: Vec<String>,
+    ) -> PyResult<Self> {
+        let mut values: Vec<Vec<Bound<'_, PyAny>>> = vec![vec![]; columns.len()];
+        for record in records {
+            let record: Vec<Bound<'_, PyAny>> = record.extract()?;
+            if record.len() != columns.len() {
+                return Err(PyValueError::new_err(format!(
+                    "Record has {} values but there are {} columns",
+                    record.len(),
+                    columns.len()
+                )));
+            }
+            for (column, value) in values.iter_mut().zip(record) {
+                column.push(value);
+            }
+        }
+        let mut item = IndexMap::new();
+        for (name, column) in columns.into_iter().zip(values) {
+            let series = Series::create(PyList::new_bound(py, column).into_any())?;
+            if item.insert(name.clone(), series).is_some() {
+                return Err(PyValueError::new_err(format!("Duplicate column {name:?}")));
+            }
+        }
+        Ok(Self { item })
+    }
+
+    /// Rows for which the predicate `expr` holds, e.g. `:price > 100 && :qty < 5`, where each
+    /// `:name` refers to the column `name`.
+    fn query(&self, expr: &str) -> PyResult<Self> {
+        let tokens =
+            exec::lexer::lex(expr.chars()).map_err(|e| PyValueError::new_err(e.to_string()))?;
+        let ast = exec::parser::parse(&tokens)
+            .map_err(|e| PyValueError::new_err(format!("Failed to parse query {expr:?}: {e}")))?;
+        let mut bindings = exec::Bindings::new();
+        for name in ast.variables() {
+            let column = self.item.get(name).ok_or_else(|| {
+                PyKeyError::new_err(format!("query references unknown column :{name}"))
+            })?;
+            bindings.insert(name.to_owned(), Arc::new(column.to_var()?));
+        }
+        let mut graph = exec::ExecutionGraph::build_execution_graph_with_bindings(&ast, &bindings)
+            .map_err(|e| PyValueError::new_err(format!("Cannot evaluate query {expr:?}: {e}")))?;
+        let handle = graph
+            .subscribe()
+            .ok_or_else(|| PyValueError::new_err("Empty query"))?;
+        if self.__len__() >= options().parallel_threshold {
+            graph.initialize_par_iter()
+        } else {
+            graph.initialize()
+        }
+        .map_err(PyValueError::new_err)?;
+        let result = handle
+            .recv()
+            .map_err(|e| PyValueError::new_err(e.to_string()))?;
+        let mask = result.bool().map_err(|_| {
+            PyValueError::new_err(format!("Query {expr:?} does not produce a Bool mask"))
+        })?;
+        let rows: Vec<usize> = match mask.len() {
+            1 if mask[0] => (0..self.__len__()).collect(),
+            1 => vec![],
+            len if len == self.__len__() => (0..len).filter(|i| mask[*i]).collect(),
+            len => {
+                return Err(PyValueError::new_err(format!(
+                    "Query mask has {len} elements but the frame has {} rows",
+                    self.__len__()
+                )))
+            }
+        };
+        Ok(self.take(&rows))
+    }
+
+    /// Rows reordered by column `by`, or by a list of columns with the first as the primary
+    /// key and later ones breaking ties. `ascending` is one direction for every key or a list
+    /// matching `by` (all ascending by default). The sort is stable, NaN sorts last in either
+    /// direction, and false sorts before true.
+    #[pyo3(signature = (by, ascending = None))]
+    fn sort_values(
+        &self,
+        by: OneOrMany<String>,
+        ascending: Option<OneOrMany<bool>>,
+    ) -> PyResult<Self> {
+        let by = by.into_vec();
+        let ascending = match ascending {
+            None => vec![true; by.len()],
+            Some(OneOrMany::One(ascending)) => vec![ascending; by.len()],
+            Some(OneOrMany::Many(ascending)) => ascending,
+        };
+        if ascending.len() != by.len() {
+            return Err(PyValueError::new_err(format!(
+                "ascending has {} entries but by has {}",
+                ascending.len(),
+                by.len()
+            )));
+        }
+        let keys = by
+            .iter()
+            .map(|name| {
+                self.item
+                    .get(name)
+                    .ok_or_else(|| PyKeyError::new_err(name.clone()))
+            })
+            .collect::<PyResult<Vec<_>>>()?;
+        let mut rows: Vec<usize> = (0..self.__len__()).collect();
+        rows.sort_by(|&i, &j| {
+            keys.iter()
+                .zip(&ascending)
+                .map(|(key, ascending)| key.cmp_rows(i, j, *ascending))
+                .find(|order| order.is_ne())
+                .unwrap_or(Ordering::Equal)
+        });
+        Ok(self.take(&rows))
+    }
+
+    /// Splits each cell of String column `column` on `sep`, emitting one row per piece and
+    /// repeating the other columns' values.
+    fn explode(&self, column: String, sep: &str) -> PyResult<Self> {
+        if sep.is_empty() {
+            return Err(PyValueError::new_err(
+                "Cannot explode on an empty separator",
+            ));
+        }
+        let cells = match self.item.get(&column) {
+            Some(Series::String(ConcreteString { items })) => items,
+            Some(_) => {
+                return Err(PyValueError::new_err(format!(
+                    "Cannot explode non-String column {column}"
+                )))
+            }
+            None => return Err(PyKeyError::new_err(column)),
+        };
+        let mut indices = vec![];
+        let mut pieces = vec![];
+        for (i, cell) in cells.iter().enumerate() {
+            // A null cell stays a single null row.
+            if !cells.is_valid(i) {
+                indices.push(i);
+                pieces.push(String::new());
+                continue;
+            }
+            for piece in cell.split(sep) {
+                indices.push(i);
+                pieces.push(piece.to_owned());
+            }
+        }
+        let mut item: IndexMap<String, Series> = self
+            .item
+            .iter()
+            .filter(|(k, _)| **k != column)
+            .map(|(k, v)| (k.clone(), v.take(&indices)))
+            .collect();
+        let validity = cells
+            .validity
+            .as_ref()
+            .map(|v| Arc::new(indices.iter().map(|i| v.get(*i) == Some(true)).collect()));
+        item.insert(
+            column,
+            Series::String(ConcreteString {
+                items: pieces.into(),
+            })
+            .with_validity(validity),
+        );
+        Ok(Self { item })
+    }
+
+    /// Joins with `other` on the key column `on`. `how` is "inner", "left", "right" or
+    /// "outer"; rows missing from one side are filled as NaN (Int columns become Float) or "".
+    /// Non-key columns present in both frames get `suffixes` appended, and `indicator` adds a
+    /// `_merge` column saying whether each row came from "left_only", "right_only" or "both".
+    /// Matched and left rows keep the left frame's order, followed by unmatched right rows.
+    #[pyo3(signature = (other, on, how = "inner", suffixes = (String::from("_x"), String::from("_y")), indicator = false))]
+    fn merge(
+        &self,
+        other: &DataFrame,
+        on: String,
+        how: &str,
+        suffixes: (String, String),
+        indicator: bool,
+    ) -> PyResult<Self> {
+        let how = Join::parse(how)?;
+        let (Some(left_key), Some(right_key)) = (self.item.get(&on), other.item.get(&on)) else {
+            return Err(PyKeyError::new_err(on));
+        };
+        let (left_valid, right_valid) = (|i| left_key.is_valid(i), |j| right_key.is_valid(j));
+        let (key, rows) = match (left_key, right_key) {
+            (Series::Int(ConcreteInt { items: l }), Series::Int(ConcreteInt { items: r })) => {
+                let (items, rows) = join_rows(l, r, left_valid, right_valid, how);
+                (
+                    Series::Int(ConcreteInt {
+                        items: items.into(),
+                    }),
+                    rows,
+                )
+            }
+            (
+                Series::String(ConcreteString { items: l }),
+                Series::String(ConcreteString { items: r }),
+            ) => {
+                let (items, rows) = join_rows(l, r, left_valid, right_valid, how);
+                (
+                    Series::String(ConcreteString {
+                        items: items.into(),
+                    }),
+                    rows,
+                )
+            }
+            _ => {
+                return Err(PyValueError::new_err(
+                    "Merge keys must both be Int or both be String",
+                ))
+            }
+        };
+        // Each key comes from the left row where there is one, so is null where that row's is.
+        let key_validity = rows
+            .iter()
+            .map(|row| match row {
+                (Some(i), _) => left_valid(*i),
+                (None, j) => j.is_some_and(right_valid),
+            })
+            .collect();
+        let key = key.with_validity(Some(Arc::new(key_validity)));
+        let (left_rows, right_rows): (Vec<_>, Vec<_>) = rows.into_iter().unzip();
+        let mut item = IndexMap::from([(on.clone(), key)]);
+        for (frame, rows, suffix, other) in [
+            (self, &left_rows, &suffixes.0, other),
+            (other, &right_rows, &suffixes.1, self),
+        ] {
+            for (name, series) in frame.item.iter().filter(|(name, _)| **name != on) {
+                let name = if other.item.contains_key(name) {
+                    format!("{name}{suffix}")
+                } else {
+                    name.clone()
+                };
+                item.insert(name, series.take_or_missing(rows));
+            }
+        }
+        if indicator {
+            let items = left_rows
+                .iter()
+                .zip(&right_rows)
+                .map(|(l, r)| match (l, r) {
+                    (Some(_), Some(_)) => "both",
+                    (Some(_), None) => "left_only",
+                    _ => "right_only",
+                })
+                .map(str::to_owned)
+                .collect();
+            item.insert(
+                "_merge".to_owned(),
+                Series::String(ConcreteString { items }),
+            );
+        }
+        Ok(Self { item })
+    }
+
+    /// Count, mean, sample standard deviation, min and max of each numeric column, ignoring
+    /// nulls and NaN. The statistics are rows, named by the leading `statistic` column; a
+    /// statistic that needs more values than the column has is NaN.
+    fn describe(&self, py: Python<'_>) -> PyResult<DataFrame> {
+        let statistics = ["count", "mean", "std", "min", "max"];
+        let mut item = IndexMap::from([(
+            "statistic".to_owned(),
+            Series::String(ConcreteString {
+                items: statistics.map(str::to_owned).to_vec().into(),
+            }),
+        )]);
+        for (name, series) in &self.item {
+            if !matches!(series, Series::Int(_) | Series::Float(_)) {
+                continue;
+            }
+            let present: Vec<usize> = (0..series.len())
+                .filter(|i| match series {
+                    Series::Float(ConcreteFloat { items }) => !items[*i].is_nan(),
+                    _ => series.is_valid(*i),
+                })
+                .collect();
+            let present = series.take(&present);
+            let extreme = |how| -> PyResult<f64> {
+                match present.len() {
+                    0 => Ok(f64::NAN),
+                    _ => present.reduce(py, how)?.extract(py),
+                }
+            };
+            let values = vec![
+                present.len() as f64,
+                present.mean().unwrap_or(f64::NAN),
+                present.std(1).unwrap_or(f64::NAN),
+                extreme(Aggregation::Min)?,
+                extreme(Aggregation::Max)?,
+            ];
+            item.insert(
+                name.clone(),
+                Series::Float(ConcreteFloat {
+                    items: values.into(),
+                }),
+            );
+        }
+        Ok(DataFrame { item })
+    }
+
+    /// Summary of the frame: row count, then each column's name, non-null count and dtype,
+    /// then the approximate memory held by the values.
+    fn info(&self) -> String {
+        let columns: Vec<&String> = self.item.keys().collect();
+        let width = columns.iter().map(|c| c.len()).max().unwrap_or(0).max(6);
+        let mut lines = vec![
+            format!("{} rows, {} columns", self.__len__(), columns.len()),
+            format!(" #  {:width$}  Non-Null Count  Dtype", "Column"),
+        ];
+        for (i, column) in columns.iter().enumerate() {
+            let series = &self.item[*column];
+            lines.push(format!(
+                "{i:>2}  {column:width$}  {:<14}  {:?}",
+                format!("{} non-null", series.non_null_count()),
+                series.dtype()
+            ));
+        }
+        let memory: usize = self.item.values().map(Series::memory_usage).sum();
+        lines.push(format!("memory usage: {memory} bytes"));
+        lines.join("\n")
+    }
+
+    /// Groups rows by the distinct values of Int or String column `by`.
+    fn groupby(&self, by: String) -> PyResult<GroupBy> {
+        if !self.item.contains_key(&by) {
+            return Err(PyKeyError::new_err(by));
+        }
+        Ok(GroupBy {
+            frame: self.clone(),
+            by,
+        })
+    }
+
+    /// Sum of numeric column `value` for each distinct value of column `by`, in key order;
+    /// shorthand for `groupby(by).agg({value: "sum"})`.
+    fn groupby_sum(&self, by: String, value: String) -> PyResult<Self> {
+        let column = |name: &String| {
+            self.item
+                .get(name)
+                .ok_or_else(|| PyKeyError::new_err(name.clone()))
+        };
+        if by == value {
+            return Err(PyValueError::new_err("Cannot sum the grouping column"));
+        }
+        let (keys, groups) = group_rows(column(&by)?)?;
+        let sums = column(&value)?.aggregate_groups(&keys, &groups, Aggregation::Sum)?;
+        Ok(Self {
+            item: IndexMap::from([(by, keys), (value, sums)]),
+        })
+    }
+
+    /// Column name to dtype name, for reading similar files with `from_csv(schema=...)`.
+    fn schema(&self) -> IndexMap<String, String> {
+        self.item
+            .iter()
+            .map(|(name, series)| (name.clone(), format!("{:?}", series.dtype())))
+            .collect()
+    }
+
+    /// Rolling window over every numeric column; non-numeric columns are dropped.
+    fn rolling(&self, window: usize) -> PyResult<Rolling> {
+        if window == 0 {
+            return Err(PyValueError::new_err("Rolling window must be at least 1"));
+        }
+        Ok(Rolling {
+            item: self.item.clone(),
+            window,
+        })
+    }
+
+    /// Reads a CSV file. When `usecols` is given, only those columns are parsed. With `sniff`
+    /// the delimiter (comma, tab or semicolon) and whether there is a header row are guessed
+    /// from the first few lines. An explicit `delimiter` or `has_header` takes precedence over
+    /// the default (comma, with a header) or the sniffed guess. Headerless columns are named
+    /// by position ("col0", "col1", ...), and columns with a blank header are skipped. Empty
+    /// fields are nulls. A `schema` as returned by `schema()` skips inference and must cover
+    /// every column read.
+    #[staticmethod]
+    #[pyo3(signature = (
+        path, usecols=None, sniff=false, schema=None, delimiter=None, has_header=None
+    ))]
+    fn from_csv(
+        path: Bound<'_, PyString>,
+        usecols: Option<Vec<String>>,
+        sniff: bool,
+        schema: Option<HashMap<String, String>>,
+        delimiter: Option<char>,
+        has_header: Option<bool>,
+    ) -> PyResult<Self> {
+        let py = path.py();
+        let path: String = path.extract()?;
+        let schema = schema
+            .map(|schema| {
+                schema
+                    .into_iter()
+                    .map(|(name, dtype)| Ok((name, Dtype::parse(&dtype)?)))
+                    .collect::<PyResult<HashMap<_, _>>>()
+            })
+            .transpose()?;
+        py.allow_threads(|| {
+            let file = File::open(path)?;
+            let reader = BufReader::new(file);
+            // TODO: move out of Box<dyn Error>
+            let options = ReadOptions {
+                usecols: usecols.as_deref(),
+                sniff,
+                delimiter,
+                has_header,
+                schema: schema.as_ref(),
+            };
+            match Self::from_csv_reader(reader, &options) {
+                Err(e) => Err(PyValueError::new_err(format!(
+                    "Failed to parse appropriately: {e}"
+                ))),
+                Ok(res) => Ok(res),
+            }
+        })
+    }
+
+    /// Reads a JSON array of objects, one row per object, with columns in the order their
+    /// keys first appear. Dtypes are inferred as in `from_csv`; a key missing from a record,
+    /// or a `null`, becomes a null.
+    #[staticmethod]
+    fn from_json(path: Bound<'_, PyString>) -> PyResult<Self> {
+        let py = path.py();
+        let path: String = path.extract()?;
+        py.allow_threads(|| {
+            let text = std::fs::read_to_string(path)?;
+            Self::from_json_str(&text)
+                .map_err(|e| PyValueError::new_err(format!("Failed to parse JSON: {e}")))
+        })
+    }
+
+    /// Writes the frame as CSV. `quoting` controls when String fields are quoted:
+    /// "minimal" (only when needed), "all" (every String field), or "none" (never, erroring
+    /// on fields that would be ambiguous).
+    #[pyo3(signature = (path, quoting = "minimal"))]
+    fn to_csv(&self, path: Bound<'_, PyString>, quoting: &str) -> PyResult<()> {
+        let py = path.py();
+        let path: String = path.extract()?;
+        let quoting = Quoting::parse(quoting)?;
+        py.allow_threads(|| {
+            let file = File::create(path)?;
+            self.to_csv_writer(BufWriter::new(file), quoting)
+                .map_err(|e| PyValueError::new_err(e.to_string()))
+        })
+    }
+}
+
+/// A Python argument given either as a single value or as a list.
+#[derive(FromPyObject)]
+enum OneOrMany<T> {
+    One(T),
+    Many(Vec<T>),
+}
+
+impl<T> OneOrMany<T> {
+    fn into_vec(self) -> Vec<T> {
+        match self {
+            Self::One(item) => vec![item],
+            Self::Many(items) => items,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Quoting {
+    Minimal,
+    All,
+    None,
+}
+
+impl Quoting {
+    fn parse(s: &str) -> PyResult<Self> {
+        match s {
+            "minimal" => Ok(Self::Minimal),
+            "all" => Ok(Self::All),
+            "none" => Ok(Self::None),
+            _ => Err(PyValueError::new_err(format!(
+                "Unknown quoting policy {s:?}; expected \"minimal\", \"all\" or \"none\""
+            ))),
+        }
+    }
+
+    /// `field` as written under this policy. A field is ambiguous if it holds a delimiter,
+    /// quote or newline, or if `from_csv` would read it back unquoted as something other than
+    /// the same String: a number, a bool, a null or the NA token.
+    fn quote(self, field: &str) -> Result<String, Box<dyn Error>> {
+        let ambiguous = field.contains([',', '"', '\n', '\r'])
+            || field.is_empty()
+            || field == options().na_token
+            || Dtype::infer(field).is_some();
+        match self {
+            Self::Minimal if !ambiguous => Ok(field.to_owned()),
+            Self::None if ambiguous => {
+                Err(format!("Field {field:?} must be quoted but quoting is \"none\"").into())
+            }
+            Self::None => Ok(field.to_owned()),
+            _ => Ok(format!("\"{}\"", field.replace('"', "\"\""))),
+        }
+    }
+}
+
+impl DataFrame {
+    fn to_csv_writer<W: Write>(
+        &self,
+        mut writer: W,
+        quoting: Quoting,
+    ) -> Result<(), Box<dyn Error>> {
+        let columns: Vec<&String> = self.item.keys().collect();
+        let header = collect_early_exit(columns.iter().copied(), |c| quoting.quote(c))?;
+        writeln!(writer, "{}", header.join(","))?;
+        let options = options();
+        let float = |x: f64| match options.precision {
+            _ if x.is_nan() => options.na_token.clone(),
+            Some(precision) => format!("{x:.precision$}"),
+            None => format!("{x:?}"),
+        };
+        for i in 0..self.__len__() {
+            let row = collect_early_exit(columns.iter().copied(), |c| match &self.item[c] {
+                // Left empty, which `from_csv` reads back as null.
+                series if !series.is_valid(i) => Ok(String::new()),
+                Series::Int(ConcreteInt { items }) => Ok(items[i].to_string()),
+                Series::Float(ConcreteFloat { items }) => Ok(float(items[i])),
+                Series::String(ConcreteString { items }) => quoting.quote(&items[i]),
+                Series::Bool(ConcreteBool { items }) => Ok(items[i].to_string()),
+            })?;
+            writeln!(writer, "{}", row.join(","))?;
+        }
+        writer.flush()?;
+        Ok(())
+    }
+
+    fn from_json_str(text: &str) -> Result<Self, Box<dyn Error>> {
+        let json::Value::Array(records) = json::parse(text)? else {
+            return Err("expected an array of records".into());
+        };
+        let mut cells: IndexMap<String, Vec<String>> = IndexMap::new();
+        for (row, record) in records.iter().enumerate() {
+            let json::Value::Object(members) = record else {
+                return Err(format!("record {row} is not an object").into());
+            };
+            for (key, value) in members {
+                let column = cells
+                    .entry(key.clone())
+                    .or_insert_with(|| vec![String::new(); row]);
+                if column.len() > row {
+                    return Err(format!("record {row} repeats key {key:?}").into());
+                }
+                column.push(json_cell(value).map_err(|e| format!("In column {key:?}: {e}"))?);
+            }
+            for column in cells.values_mut() {
+                column.resize(row + 1, String::new());
+            }
+        }
+        let item = cells
+            .into_iter()
+            .map(|(name, cells)| {
+                let series =
+                    Series::from_untyped(&cells).map_err(|e| format!("In column {name:?}: {e}"))?;
+                Ok((name, series))
+            })
+            .collect::<Result<_, Box<dyn Error>>>()?;
+        Ok(Self { item })
+    }
+
+    /// Rows `rows` of every column, in that order.
+    fn take(&self, rows: &[usize]) -> Self {
+        let item = self
+            .item
+            .iter()
+            .map(|(k, v)| (k.clone(), v.take(rows)))
+            .collect();
+        Self { item }
+    }
+
+    fn row<'py>(&self, py: Python<'py>, index: usize) -> PyResult<Bound<'py, PyDict>> {
+        let row = PyDict::new_bound(py);
+        for (k, v) in &self.item {
+            row.set_item(k, v.get(py, index))?;
+        }
+        Ok(row)
+    }
+
+    // Ideally use serde but I'm on a flight and cannot `cargo add`
+    fn from_csv_reader<R: Read>(
+        mut buf_reader: BufReader<R>,
+        options: &ReadOptions<'_>,
+    ) -> Result<Self, Box<dyn Error>> {
+        let ReadOptions {
+            usecols,
+            sniff,
+            delimiter,
+            has_header,
+            schema,
+        } = *options;
+        // Sampled lines keep their terminators so the `csv` reader can be handed them back.
+        let mut sample = vec![];
+        if sniff {
+            for _ in 0..Dialect::SNIFF_LINES {
+                let mut line = String::new();
+                if buf_reader.read_line(&mut line)? == 0 {
+                    break;
+                }
+                sample.push(line);
+            }
+        }
+        let mut dialect = if sniff {
+            let lines: Vec<String> = sample
+                .iter()
+                .map(|l| l.trim_end_matches(['\n', '\r']).to_owned())
+                .collect();
+            Dialect::sniff(&lines)
+        } else {
+            Dialect::default()
+        };
+        if let Some(delimiter) = delimiter {
+            dialect.delimiter = delimiter;
+        }
+        if let Some(has_header) = has_header {
+            dialect.has_header = has_header;
+        }
+        let mut records = dialect.records(sample, buf_reader)?.peekable();
+        let header = if dialect.has_header {
+            records.next()
+        } else {
+            match records.peek() {
+                Some(Ok(fields)) => {
+                    Some(Ok((0..fields.len()).map(|i| format!("col{i}")).collect()))
+                }
+                _ => records.next(),
+            }
+        };
+        if let Some(header_elements) = header {
+            let header_elements: Vec<String> = header_elements?
+                .iter()
+                .map(|s| s.trim().to_owned())
+                .collect();
+            if let Some(unknown) = usecols
+                .unwrap_or_default()
+                .iter()
+                .find(|c| !header_elements.contains(c))
+            {
+                return Err(format!("Unknown column {unknown:?} in usecols").into());
+            }
+            // Columns without a name, like those a delimiter at each end of every line
+            // produces, can't be addressed and are skipped.
+            let mut str_items: IndexMap<&str, Vec<String>> = header_elements
+                .iter()
+                .filter(|s| !s.is_empty() && usecols.is_none_or(|cols| cols.contains(s)))
+                .map(|s| (s.as_str(), vec![]))
+                .collect();
+            for fields in records {
+                let fields = fields?;
+                if fields.len() != header_elements.len() {
+                    return Err("Incompatible row length with number of columns in header".into());
+                }
+                for (key, tok) in header_elements.iter().zip(fields) {
+                    if let Some(column) = str_items.get_mut(key.as_str()) {
+                        column.push(tok);
+                    }
+                }
+            }
+            let mut item: IndexMap<String, Series> = IndexMap::new();
+            for (k, v) in str_items.into_iter() {
+                let series = match schema {
+                    None => Series::from_untyped(v.as_slice())
+                        .map_err(|e| format!("In column {k:?}: {e}"))?,
+                    Some(schema) => {
+                        let dtype = schema
+                            .get(k)
+                            .ok_or_else(|| format!("Column {k:?} is missing from the schema"))?;
+                        Series::from_typed(v.as_slice(), *dtype)
+                            .map_err(|e| format!("In column {k:?}: {e}"))?
+                    }
+                };
+                item.insert(k.to_owned(), series);
+            }
+
+            return Ok(Self { item });
+        } else {
+            return Err("Empty buffer".into());
+        }
+    }
+}
+
+/// What `from_csv_reader` reads and how.
+#[derive(Clone, Copy, Default)]
+struct ReadOptions<'a> {
+    /// Only these columns are parsed.
+    usecols: Option<&'a [String]>,
+    /// Guess the dialect from the first few lines.
+    sniff: bool,
+    /// Field separator, overriding the default or sniffed one.
+    delimiter: Option<char>,
+    /// Whether the first line names the columns, overriding the default (true) or sniffed
+    /// guess. Without a header, columns are named by position: "col0", "col1", ...
+    has_header: Option<bool>,
+    /// Each column's dtype, skipping inference.
+    schema: Option<&'a HashMap<String, Dtype>>,
+}
+
+/// Fields of each CSV row, as produced by `Dialect::records`.
+type Records<'r> = Box<dyn Iterator<Item = Result<Vec<String>, Box<dyn Error>>> + 'r>;
+
+/// A field unquoted by the `csv` crate, quoted again unless it reads as a non-String.
+#[cfg(feature = "csv")]
+fn requote(field: &str) -> String {
+    match Dtype::infer(field) {
+        Some(Dtype::Int | Dtype::Float | Dtype::Bool) => field.to_owned(),
+        _ if field.is_empty() => String::new(),
+        _ => format!("\"{}\"", field.replace('"', "\"\"")),
+    }
+}
+
+/// How fields and rows are laid out in a CSV file.
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Dialect {
+    delimiter: char,
+    has_header: bool,
+}
+
+impl Default for Dialect {
+    fn default() -> Self {
+        Self {
+            delimiter: ',',
+            has_header: true,
+        }
+    }
+}
+
+impl Dialect {
+    /// Number of leading lines `sniff` looks at.
+    const SNIFF_LINES: usize = 5;
+
+    /// Rows of the `sample` lines followed by the rest of `reader`, split into fields. Quoted
+    /// data fields keep their quotes, which inference relies on to recognise Strings that
+    /// would otherwise read as numbers or bools; header names are unquoted.
+    #[cfg(not(feature = "csv"))]
+    fn records<'r, R: Read + 'r>(
+        self,
+        sample: Vec<String>,
+        reader: BufReader<R>,
+    ) -> Result<Records<'r>, Box<dyn Error>> {
+        let sample = sample
+            .into_iter()
+            .map(|line| Ok(line.trim_end_matches(['\n', '\r']).to_owned()));
+        let header = self.has_header;
+        Ok(Box::new(sample.chain(reader.lines()).enumerate().map(
+            move |(i, line)| {
+                let line = line?;
+                let fields = self.fields(&line);
+                Ok(if header && i == 0 {
+                    fields
+                        .map(|field| match field.trim() {
+                            name if Dtype::infer(name) == Some(Dtype::String) => unquote(name),
+                            name => name.to_owned(),
+                        })
+                        .collect()
+                } else {
+                    fields.map(str::to_owned).collect()
+                })
+            },
+        )))
+    }
+
+    /// Rows read by the `csv` crate, which also handles newlines inside quoted fields. Its
+    /// fields come back unquoted, so a data field is re-quoted as a String unless it reads as
+    /// a number or bool: `"1"` is an Int here, and unquoted text is a String.
+    #[cfg(feature = "csv")]
+    fn records<'r, R: Read + 'r>(
+        self,
+        sample: Vec<String>,
+        reader: BufReader<R>,
+    ) -> Result<Records<'r>, Box<dyn Error>> {
+        let delimiter = u8::try_from(self.delimiter)
+            .map_err(|_| format!("Delimiter {:?} is not ASCII", self.delimiter))?;
+        let reader = csv::ReaderBuilder::new()
+            .delimiter(delimiter)
+            .has_headers(false)
+            .flexible(true)
+            .from_reader(std::io::Cursor::new(sample.concat()).chain(reader));
+        let header = self.has_header;
+        Ok(Box::new(reader.into_records().enumerate().map(
+            move |(i, record)| {
+                let record = record?;
+                Ok(if header && i == 0 {
+                    record.iter().map(str::to_owned).collect()
+                } else {
+                    record.iter().map(requote).collect()
+                })
+            },
+        )))
+    }
+
+    /// Guesses the dialect of `lines`. The delimiter is the one splitting every line into
+    /// the same number of fields, preferring more fields and then comma; there is a header
+    /// unless the first line has a numeric field.
+    fn sniff(lines: &[String]) -> Self {
+        let lines: Vec<&String> = lines.iter().filter(|l| !l.is_empty()).collect();
+        // `max_by_key` keeps the last of equal keys, so comma goes last to win ties.
+        let delimiter = [';', '\t', ',']
+            .into_iter()
+            .filter_map(|delimiter| {
+                let dialect = Self {
+                    delimiter,
+                    has_header: true,
+                };
+                let counts: HashSet<usize> =
+                    lines.iter().map(|l| dialect.fields(l).count()).collect();
+                match Vec::from_iter(counts)[..] {
+                    [n] if n > 1 => Some((n, delimiter)),
+                    _ => None,
+                }
+            })
+            .max_by_key(|(n, _)| *n)
+            .map_or(',', |(_, delimiter)| delimiter);
+        let dialect = Self {
+            delimiter,
+            has_header: true,
+        };
+        let has_header = lines.first().is_none_or(|first| {
+            !dialect
+                .fields(first)
+                .any(|field| matches!(Dtype::infer(field.trim()), Some(Dtype::Int | Dtype::Float)))
+        });
+        Self {
+            delimiter,
+            has_header,
+        }
+    }
+
+    /// Splits `line` on the delimiter where it is outside double quotes, so a leading or
+    /// trailing delimiter yields an empty field. Quoted fields keep their quotes, which
+    /// inference relies on to recognise Strings.
+    fn fields<'a>(&self, line: &'a str) -> std::vec::IntoIter<&'a str> {
+        let (mut fields, mut start, mut quoted) = (vec![], 0, false);
+        for (i, c) in line.char_indices() {
+            if c == '"' {
+                // An escaped `""` toggles twice, leaving the state unchanged.
+                quoted = !quoted;
+            } else if c == self.delimiter && !quoted {
+                fields.push(&line[start..i]);
+                start = i + c.len_utf8();
+            }
+        }
+        fields.push(&line[start..]);
+        fields.into_iter()
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Join {
+    Inner,
+    Left,
+    Right,
+    Outer,
+}
+
+impl Join {
+    fn parse(s: &str) -> PyResult<Self> {
+        match s {
+            "inner" => Ok(Self::Inner),
+            "left" => Ok(Self::Left),
+            "right" => Ok(Self::Right),
+            "outer" => Ok(Self::Outer),
+            _ => Err(PyValueError::new_err(format!("Unknown join {s:?}"))),
+        }
+    }
+}
+
+type JoinedRows = Vec<(Option<usize>, Option<usize>)>;
+
+/// Pairs up rows of `left` and `right` with equal keys, returning each output row's key and
+/// its (left, right) row indices, where `None` means that side had no match. Keys for which
+/// `left_valid` or `right_valid` is false are nulls, which match nothing.
+fn join_rows<T: Hash + Eq + Clone>(
+    left: &[T],
+    right: &[T],
+    left_valid: impl Fn(usize) -> bool,
+    right_valid: impl Fn(usize) -> bool,
+    how: Join,
+) -> (Vec<T>, JoinedRows) {
+    let mut lookup: HashMap<&T, Vec<usize>> = HashMap::new();
+    for (j, key) in right.iter().enumerate().filter(|(j, _)| right_valid(*j)) {
+        lookup.entry(key).or_default().push(j);
+    }
+    let mut matched = vec![false; right.len()];
+    let (mut keys, mut rows) = (vec![], vec![]);
+    for (i, key) in left.iter().enumerate() {
+        match lookup.get(key).filter(|_| left_valid(i)) {
+            Some(js) => {
+                for j in js {
+                    matched[*j] = true;
+                    keys.push(key.clone());
+                    rows.push((Some(i), Some(*j)));
+                }
+            }
+            None if matches!(how, Join::Left | Join::Outer) => {
+                keys.push(key.clone());
+                rows.push((Some(i), None));
+            }
+            None => {}
+        }
+    }
+    if matches!(how, Join::Right | Join::Outer) {
+        for (j, key) in right.iter().enumerate().filter(|(j, _)| !matched[*j]) {
+            keys.push(key.clone());
+            rows.push((None, Some(j)));
+        }
+    }
+    (keys, rows)
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Aggregation {
+    Sum,
+    Mean,
+    Min,
+    Max,
+    Count,
+}
+
+impl Aggregation {
+    fn parse(s: &str) -> PyResult<Self> {
+        match s {
+            "sum" => Ok(Self::Sum),
+            "mean" => Ok(Self::Mean),
+            "min" => Ok(Self::Min),
+            "max" => Ok(Self::Max),
+            "count" => Ok(Self::Count),
+            _ => Err(PyValueError::new_err(format!("Unknown aggregation {s:?}"))),
+        }
+    }
+}
+
+/// Distinct values of `key` in sorted order, with the row indices holding each value. Rows
+/// with a null key belong to no group.
+fn group_rows(key: &Series) -> PyResult<(Series, Vec<Vec<usize>>)> {
+    fn group<T: Ord + Clone>(
+        items: &[T],
+        valid: impl Fn(usize) -> bool,
+    ) -> (Vec<T>, Vec<Vec<usize>>) {
+        let mut groups: BTreeMap<&T, Vec<usize>> = BTreeMap::new();
+        for (i, item) in items.iter().enumerate().filter(|(i, _)| valid(*i)) {
+            groups.entry(item).or_default().push(i);
+        }
+        groups.into_iter().map(|(k, v)| (k.clone(), v)).unzip()
+    }
+    let valid = |i| key.is_valid(i);
+    match key {
+        Series::Int(ConcreteInt { items }) => {
+            let (items, groups) = group(items, valid);
+            Ok((
+                Series::Int(ConcreteInt {
+                    items: items.into(),
+                }),
+                groups,
+            ))
+        }
+        Series::String(ConcreteString { items }) => {
+            let (items, groups) = group(items, valid);
+            Ok((
+                Series::String(ConcreteString {
+                    items: items.into(),
+                }),
+                groups,
+            ))
+        }
+        Series::Bool(ConcreteBool { items }) => {
+            let (items, groups) = group(items, valid);
+            Ok((
+                Series::Bool(ConcreteBool {
+                    items: items.into(),
+                }),
+                groups,
+            ))
+        }
+        Series::Float(_) => Err(PyValueError::new_err("Cannot group by a Float column")),
+    }
+}
+
+impl Series {
+    /// Aggregates the rows of each group into one element per group. `keys` names the groups
+    /// in errors.
+    fn aggregate_groups(
+        &self,
+        keys: &Series,
+        groups: &[Vec<usize>],
+        how: Aggregation,
+    ) -> PyResult<Self> {
+        /// Folds each group with `f`, giving None for an empty group.
+        fn fold<'a, T: Copy>(
+            items: &'a [T],
+            groups: &'a [Vec<usize>],
+            f: impl Fn(T, T) -> T + 'a,
+        ) -> impl Iterator<Item = Option<T>> + 'a {
+            groups
+                .iter()
+                .map(move |g| g.iter().map(|i| items[*i]).reduce(&f))
+        }
+        // Nulls take no part. A group with no other values sums to zero and is null under
+        // the other aggregations.
+        let groups: Vec<Vec<usize>> = groups
+            .iter()
+            .map(|g| g.iter().copied().filter(|i| self.is_valid(*i)).collect())
+            .collect();
+        let groups = groups.as_slice();
+        let aggregated = match (self, how) {
+            (_, Aggregation::Count) => Self::Int(ConcreteInt {
+                items: groups.iter().map(|g| g.len() as i64).collect(),
+            }),
+            (Self::Int(ConcreteInt { items }), Aggregation::Sum) => {
+                let sums = groups
+                    .iter()
+                    .enumerate()
+                    .map(|(g, rows)| {
+                        let sum = rows
+                            .iter()
+                            .try_fold(0i64, |acc, i| acc.checked_add(items[*i]));
+                        sum.ok_or_else(|| {
+                            PyOverflowError::new_err(format!(
+                                "Sum overflows Int in group {}",
+                                keys.element_repr(g)
+                            ))
+                        })
+                    })
+                    .collect::<PyResult<_>>()?;
+                Self::Int(ConcreteInt { items: sums })
+            }
+            (Self::Int(ConcreteInt { items }), Aggregation::Min) => Self::Int(ConcreteInt {
+                items: fold(items, groups, i64::min)
+                    .map(Option::unwrap_or_default)
+                    .collect(),
+            }),
+            (Self::Int(ConcreteInt { items }), Aggregation::Max) => Self::Int(ConcreteInt {
+                items: fold(items, groups, i64::max)
+                    .map(Option::unwrap_or_default)
+                    .collect(),
+            }),
+            (Self::Float(ConcreteFloat { items }), Aggregation::Sum) => {
+                Self::Float(ConcreteFloat {
+                    items: fold(items, groups, |a, b| a + b)
+                        .map(|sum| sum.unwrap_or(0.0))
+                        .collect(),
+                })
+            }
+            (Self::Float(ConcreteFloat { items }), Aggregation::Min) => {
+                Self::Float(ConcreteFloat {
+                    items: fold(items, groups, f64::min)
+                        .map(|min| min.unwrap_or(f64::NAN))
+                        .collect(),
+                })
+            }
+            (Self::Float(ConcreteFloat { items }), Aggregation::Max) => {
+                Self::Float(ConcreteFloat {
+                    items: fold(items, groups, f64::max)
+                        .map(|max| max.unwrap_or(f64::NAN))
+                        .collect(),
+                })
+            }
+            (Self::Int(_) | Self::Float(_), Aggregation::Mean) => {
+                let values = self.f64_values()?;
+                Self::Float(ConcreteFloat {
+                    items: fold(&values, groups, |a, b| a + b)
+                        .zip(groups)
+                        .map(|(sum, g)| sum.map_or(f64::NAN, |sum| sum / g.len() as f64))
+                        .collect(),
+                })
+            }
+            (Self::String(ConcreteString { items }), Aggregation::Min | Aggregation::Max) => {
+                Self::String(ConcreteString {
+                    items: groups
+                        .iter()
+                        .map(|g| {
+                            let group = g.iter().map(|i| &items[*i]);
+                            let extreme = if how == Aggregation::Min {
+                                group.min()
+                            } else {
+                                group.max()
+                            };
+                            extreme.cloned().unwrap_or_default()
+                        })
+                        .collect(),
+                })
+            }
+            (Self::String(_) | Self::Bool(_), _) => {
+                return Err(PyValueError::new_err(format!(
+                    "Cannot {how:?} a {:?} column",
+                    self.dtype()
+                )))
+            }
+        };
+        let validity = matches!(how, Aggregation::Min | Aggregation::Max | Aggregation::Mean)
+            .then(|| Arc::new(groups.iter().map(|g| !g.is_empty()).collect()));
+        Ok(aggregated.with_validity(validity))
+    }
+}
+
+#[pyclass]
+struct Rows {
+    frame: DataFrame,
+    index: usize,
+}
+
+#[pymethods]
+impl Rows {
+    fn __iter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
+        slf
+    }
+
+    fn __next__<'py>(&mut self, py: Python<'py>) -> PyResult<Option<Bound<'py, PyDict>>> {
+        if self.index >= self.frame.__len__() {
+            return Ok(None);
+        }
+        self.index += 1;
+        self.frame.row(py, self.index - 1).map(Some)
+    }
+}
+
+/// Grows a Series in place, one element at a time. `Series` itself can't be mutated from
+/// Python, since pyo3 freezes enum pyclasses.
+#[pyclass]
+struct SeriesBuilder {
+    series: Series,
+}
+
+#[pymethods]
+impl SeriesBuilder {
+    #[new]
+    fn create(series: Series) -> Self {
+        Self { series }
+    }
+
+    fn __len__(&self) -> usize {
+        self.series.len()
+    }
+
+    /// Appends `value`, which must match the dtype, except that an int may be pushed onto a
+    /// Float series.
+    fn push(&mut self, value: &Bound<'_, PyAny>) -> PyResult<()> {
+        self.series.push(value)
+    }
+
+    /// The series built so far.
+    fn finish(&self) -> Series {
+        self.series.clone()
+    }
+}
+
+#[pyclass]
+struct GroupBy {
+    frame: DataFrame,
+    by: String,
+}
+
+#[pymethods]
+impl GroupBy {
+    /// One row per group with the key column followed by each `{column: aggregation}`, where
+    /// the aggregation is one of "sum", "mean", "min", "max" or "count".
+    fn agg(&self, aggregations: Bound<'_, PyDict>) -> PyResult<DataFrame> {
+        let (keys, groups) = group_rows(&self.frame.item[&self.by])?;
+        let mut item = IndexMap::from([(self.by.clone(), keys.clone())]);
+        for (column, how) in aggregations {
+            let column: String = column.extract()?;
+            let how = Aggregation::parse(&how.extract::<String>()?)?;
+            let series = self
+                .frame
+                .item
+                .get(&column)
+                .ok_or_else(|| PyKeyError::new_err(column.clone()))?;
+            item.insert(column, series.aggregate_groups(&keys, &groups, how)?);
+        }
+        Ok(DataFrame { item })
+    }
+}
+
+#[pyclass]
+struct Rolling {
+    item: IndexMap<String, Series>,
+    window: usize,
+}
+
+#[pymethods]
+impl Rolling {
+    fn sum(&self) -> DataFrame {
+        let item = self
+            .item
+            .iter()
+            .filter_map(|(k, v)| Some((k.clone(), v.rolling_sum(self.window)?)))
+            .collect();
+        DataFrame { item }
+    }
+
+    fn mean(&self) -> DataFrame {
+        let window = self.window as f64;
+        let mut frame = self.sum();
+        for series in frame.item.values_mut() {
+            if let Series::Float(ConcreteFloat { items }) = series {
+                items.iter_mut().for_each(|v| *v /= window);
+            }
+        }
+        frame
+    }
+}
+
+// impl IntoPy<PyObject> for Series {
+//     fn into_py(self, py: Python<'_>) -> PyObject {
+//         match self {
+//             Self::Int(concrete) => concrete.into_py(py),
+//             Self::Float(concrete) => concrete.into_py(py),
+//             Self::String(concrete) => concrete.into_py(py),
+//         }
+//     }
+// }
+
+/// Module-wide settings, exposed to Python as `dfrs.options`.
+#[derive(Clone, Debug, PartialEq)]
+struct Config {
+    /// Decimal places for floats written by `to_csv`; `None` writes the shortest form that
+    /// reads back exactly.
+    precision: Option<usize>,
+    /// CSV cell standing for a missing value: read as NaN, and written for NaN.
+    na_token: String,
+    /// Row count from which `query` evaluates its expression graph on the thread pool.
+    parallel_threshold: usize,
+    /// Reject CSV columns mixing Int and Float cells rather than promoting them to Float.
+    strict_inference: bool,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            precision: None,
+            na_token: "NaN".to_owned(),
+            parallel_threshold: 100_000,
+            strict_inference: false,
+        }
+    }
+}
+
+fn config() -> &'static RwLock<Config> {
+    static CONFIG: OnceLock<RwLock<Config>> = OnceLock::new();
+    CONFIG.get_or_init(Default::default)
+}
+
+/// Snapshot of the current settings.
+fn options() -> Config {
+    config().read().unwrap().clone()
+}
+
+/// Python handle on the global `Config`. `save` returns every setting as a dict, which
+/// `restore` accepts (in full or in part) to put them back.
+#[pyclass]
+struct Options;
+
+#[pymethods]
+impl Options {
+    #[getter]
+    fn precision(&self) -> Option<usize> {
+        options().precision
+    }
+
+    #[setter]
+    fn set_precision(&self, value: Option<usize>) {
+        config().write().unwrap().precision = value;
+    }
+
+    #[getter]
+    fn na_token(&self) -> String {
+        options().na_token
+    }
+
+    #[setter]
+    fn set_na_token(&self, value: String) -> PyResult<()> {
+        if value.contains([',', '"', '\n']) {
+            return Err(PyValueError::new_err(format!(
+                "NA token {value:?} would need quoting in CSV"
+            )));
+        }
+        config().write().unwrap().na_token = value;
+        Ok(())
+    }
+
+    #[getter]
+    fn parallel_threshold(&self) -> usize {
+        options().parallel_threshold
+    }
+
+    #[setter]
+    fn set_parallel_threshold(&self, value: usize) {
+        config().write().unwrap().parallel_threshold = value;
+    }
+
+    #[getter]
+    fn strict_inference(&self) -> bool {
+        options().strict_inference
+    }
+
+    #[setter]
+    fn set_strict_inference(&self, value: bool) {
+        config().write().unwrap().strict_inference = value;
+    }
+
+    fn save<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        let Config {
+            precision,
+            na_token,
+            parallel_threshold,
+            strict_inference,
+        } = options();
+        let state = PyDict::new_bound(py);
+        state.set_item("precision", precision)?;
+        state.set_item("na_token", na_token)?;
+        state.set_item("parallel_threshold", parallel_threshold)?;
+        state.set_item("strict_inference", strict_inference)?;
+        Ok(state)
+    }
+
+    fn restore(&self, state: Bound<'_, PyDict>) -> PyResult<()> {
+        for (key, value) in state {
+            match key.extract::<String>()?.as_str() {
+                "precision" => self.set_precision(value.extract()?),
+                "na_token" => self.set_na_token(value.extract()?)?,
+                "parallel_threshold" => self.set_parallel_threshold(value.extract()?),
+                "strict_inference" => self.set_strict_inference(value.extract()?),
+                other => return Err(PyKeyError::new_err(format!("Unknown option {other:?}"))),
+            }
+        }
+        Ok(())
+    }
+
+    fn __repr__(&self) -> String {
+        format!("{:?}", options())
+    }
+}
+
+#[pymodule]
+fn dfrs(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add("options", Options)?;
+    m.add_class::<DataFrame>()?;
+    m.add_class::<Series>()?;
+    m.add_class::<SeriesBuilder>()?;
+    m.add_class::<Rolling>()?;
+    m.add_class::<GroupBy>()?;
+    m.add_class::<Rows>()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pyo3::types::IntoPyDict;
+
+    #[test]
+    fn test_mode() {
+        let series = Series::Int(ConcreteInt {
+            items: vec![1, 2, 2, 3, 3].into(),
+        });
+        assert_eq!(series.modes().__repr__(), "IntSeries([2, 3])");
+        let series = Series::String(ConcreteString {
+            items: vec!["b".to_owned(), "a".to_owned(), "b".to_owned()].into(),
+        });
+        assert_eq!(series.modes().__repr__(), "StringSeries([\"b\"])");
+
+        let series = Series::Bool(ConcreteBool {
+            items: vec![true, false, true].into(),
+        });
+        pyo3::prepare_freethreaded_python();
+        Python::with_gil(|py| {
+            let mode = series.mode(py).into_bound(py);
+            assert!(mode.is_instance_of::<PyBool>());
+            assert!(mode.extract::<bool>().unwrap());
+        });
+    }
+
+    #[test]
+    fn test_fillna() {
+        let floats = Series::Float(ConcreteFloat {
+            items: vec![1.5, f64::NAN, 2.0].into(),
+        });
+        let ints = Series::Int(ConcreteInt {
+            items: vec![1, 0, 3].into(),
+        })
+        .take_or_null(&[Some(0), None, Some(2)]);
+        pyo3::prepare_freethreaded_python();
+        Python::with_gil(|py| {
+            let fill = |series: &Series, value: PyObject| series.fillna(value.into_bound(py));
+            assert_eq!(
+                fill(&floats, 0.0.into_py(py)).unwrap().__repr__(),
+                "FloatSeries([1.5, 0.0, 2.0])"
+            );
+            let filled = fill(&ints, 7.into_py(py)).unwrap();
+            assert_eq!(filled.__repr__(), "IntSeries([1, 7, 3])");
+            assert!(filled.validity().is_none());
+            assert_eq!(
+                fill(&ints, 0.5.into_py(py)).unwrap().__repr__(),
+                "FloatSeries([1.0, 0.5, 3.0])"
+            );
+            assert!(fill(&ints, "x".into_py(py)).is_err());
+            assert!(fill(&ints, true.into_py(py)).is_err());
+
+            let complete = Series::Int(ConcreteInt {
+                items: vec![1, 2].into(),
+            });
+            match (fill(&complete, 0.into_py(py)).unwrap(), &complete) {
+                (Series::Int(ConcreteInt { items: a }), Series::Int(ConcreteInt { items: b })) => {
+                    assert!(Arc::ptr_eq(&a.items, &b.items))
+                }
+                _ => unreachable!(),
+            }
+        });
+    }
+
+    #[test]
+    fn test_describe() {
+        let df = DataFrame {
+            item: IndexMap::from([
+                (
+                    "n".to_owned(),
+                    Series::Int(ConcreteInt {
+                        items: vec![1, 2, 3, 0].into(),
+                    })
+                    .take_or_null(&[Some(0), Some(1), Some(2), None]),
+                ),
+                (
+                    "name".to_owned(),
+                    Series::String(ConcreteString {
+                        items: ["a", "b", "c", "d"].map(str::to_owned).to_vec().into(),
+                    }),
+                ),
+                (
+                    "x".to_owned(),
+                    Series::Float(ConcreteFloat {
+                        items: vec![f64::NAN, 0.5, f64::NAN, f64::NAN].into(),
+                    }),
+                ),
+            ]),
+        };
+        pyo3::prepare_freethreaded_python();
+        let summary = Python::with_gil(|py| df.describe(py)).unwrap();
+        assert_eq!(summary.columns(), ["statistic", "n", "x"]);
+        assert_eq!(
+            summary.item["n"].__repr__(),
+            "FloatSeries([3.0, 2.0, 1.0, 1.0, 3.0])"
+        );
+        assert_eq!(
+            summary.item["x"].__repr__(),
+            "FloatSeries([1.0, 0.5, NaN, 0.5, 0.5])"
+        );
+    }
+
+    #[test]
+    fn test_series_concat() {
+        let ints = Series::Int(ConcreteInt {
+            items: vec![1, 2].into(),
+        });
+        let floats = Series::Float(ConcreteFloat {
+            items: vec![0.5].into(),
+        });
+        let strings = Series::String(ConcreteString {
+            items: vec!["a".to_owned()].into(),
+        });
+        assert_eq!(
+            Series::concat(vec![ints.clone(), ints.clone()])
+                .unwrap()
+                .__repr__(),
+            "IntSeries([1, 2, 1, 2])"
+        );
+        assert_eq!(
+            Series::concat(vec![ints.clone(), floats.clone()])
+                .unwrap()
+                .__repr__(),
+            "FloatSeries([1.0, 2.0, 0.5])"
+        );
+        assert!(Series::concat(vec![ints.clone(), strings]).is_err());
+        assert!(Series::concat(vec![]).is_err());
+        let with_null = floats.take_or_null(&[None]);
+        assert_eq!(
+            Series::concat(vec![ints, with_null]).unwrap().__repr__(),
+            "FloatSeries([1.0, 2.0, None])"
+        );
+    }
+
+    #[test]
+    fn test_frame_concat() {
+        let frame = |ids: Vec<i64>, scores: Series| DataFrame {
+            item: IndexMap::from([
+                (
+                    "id".to_owned(),
+                    Series::Int(ConcreteInt { items: ids.into() }),
+                ),
+                ("score".to_owned(), scores),
+            ]),
+        };
+        let a = frame(
+            vec![1, 2],
+            Series::Int(ConcreteInt {
+                items: vec![10, 20].into(),
+            }),
+        );
+        let b = frame(
+            vec![3],
+            Series::Float(ConcreteFloat {
+                items: vec![0.5].into(),
+            }),
+        );
+        let joined = DataFrame::concat(vec![a.clone(), b]).unwrap();
+        assert_eq!(joined.__len__(), 3);
+        assert_eq!(joined.item["id"].__repr__(), "IntSeries([1, 2, 3])");
+        assert_eq!(
+            joined.item["score"].__repr__(),
+            "FloatSeries([10.0, 20.0, 0.5])"
+        );
+        let narrow = DataFrame {
+            item: IndexMap::from([("id".to_owned(), a.item["id"].clone())]),
+        };
+        assert!(DataFrame::concat(vec![a, narrow]).is_err());
+        assert!(DataFrame::concat(vec![]).is_err());
+    }
+
+    #[test]
+    fn test_unique() {
+        let series = Series::Int(ConcreteInt {
+            items: vec![3, 1, 3, 2, 1].into(),
+        });
+        assert_eq!(series.unique().__repr__(), "IntSeries([3, 1, 2])");
+        let series = Series::String(ConcreteString {
+            items: ["b", "a", "b", "", "a"].map(str::to_owned).to_vec().into(),
+        });
+        assert_eq!(
+            series.unique().__repr__(),
+            "StringSeries([\"b\", \"a\", \"\"])"
+        );
+        let series = Series::Float(ConcreteFloat {
+            items: vec![f64::NAN, 0.0, f64::NAN, -0.0].into(),
+        });
+        assert_eq!(series.unique().__repr__(), "FloatSeries([NaN, 0.0, -0.0])");
+        let series = series.take_or_null(&[Some(1), None, Some(1), None]);
+        assert_eq!(series.unique().__repr__(), "FloatSeries([0.0, None])");
+    }
+
+    #[test]
+    fn test_neg() {
+        let series = Series::Int(ConcreteInt {
+            items: vec![-1, 0, 3].into(),
+        });
+        assert_eq!(
+            series.__neg__().unwrap().__repr__(),
+            "IntSeries([1, 0, -3])"
+        );
+        let series = Series::Float(ConcreteFloat {
+            items: vec![1.5, -0.0, f64::INFINITY].into(),
+        });
+        assert_eq!(
+            series.__neg__().unwrap().__repr__(),
+            "FloatSeries([-1.5, 0.0, -inf])"
+        );
+        let series = Series::Int(ConcreteInt {
+            items: vec![i64::MIN].into(),
+        });
+        assert!(series.__neg__().is_err());
+        let series = Series::String(ConcreteString {
+            items: vec!["a".to_owned()].into(),
+        });
+        assert!(series.__neg__().is_err());
+    }
+
+    #[test]
+    fn test_round() {
+        let series = Series::Float(ConcreteFloat {
+            items: vec![1.2345, 2.3456].into(),
+        });
+        assert_eq!(
+            series.round(2).unwrap().__repr__(),
+            "FloatSeries([1.23, 2.35])"
+        );
+        let series = Series::Float(ConcreteFloat {
+            items: vec![0.5, 1.5, 2.5, 2.675, -0.125, f64::NAN].into(),
+        });
+        assert_eq!(
+            series.round(0).unwrap().__repr__(),
+            "FloatSeries([0.0, 2.0, 2.0, 3.0, -0.0, NaN])"
+        );
+        assert_eq!(
+            series.round(2).unwrap().__repr__(),
+            "FloatSeries([0.5, 1.5, 2.5, 2.67, -0.12, NaN])"
+        );
+        let series = Series::Float(ConcreteFloat {
+            items: vec![1234.5, 250.0, 350.0, 1e300].into(),
+        });
+        assert_eq!(
+            series.round(-2).unwrap().__repr__(),
+            "FloatSeries([1200.0, 200.0, 400.0, 1e300])"
+        );
+        assert_eq!(
+            series.round(-400).unwrap().__repr__(),
+            "FloatSeries([0.0, 0.0, 0.0, 0.0])"
+        );
+        let series = Series::Float(ConcreteFloat {
+            items: vec![f64::MAX].into(),
+        });
+        assert!(series.round(-308).is_err());
+        let series = Series::Int(ConcreteInt {
+            items: vec![15].into(),
+        });
+        assert_eq!(series.round(-1).unwrap().__repr__(), "IntSeries([15])");
+        let series = Series::String(ConcreteString {
+            items: vec!["1.5".to_owned()].into(),
+        });
+        assert!(series.round(0).is_err());
+    }
+
+    #[test]
+    fn test_abs() {
+        let series = Series::Int(ConcreteInt {
+            items: vec![-1, 2, -3].into(),
+        });
+        assert_eq!(series.abs().unwrap().__repr__(), "IntSeries([1, 2, 3])");
+        let series = Series::Float(ConcreteFloat {
+            items: vec![-0.5, f64::NAN, f64::NEG_INFINITY].into(),
+        });
+        assert_eq!(
+            series.abs().unwrap().__repr__(),
+            "FloatSeries([0.5, NaN, inf])"
+        );
+        let series = Series::Int(ConcreteInt {
+            items: vec![i64::MIN].into(),
+        });
+        assert!(series.abs().is_err());
+        let series = Series::Bool(ConcreteBool {
+            items: vec![true].into(),
+        });
+        assert!(series.abs().is_err());
+    }
+
+    #[test]
+    fn test_to_list() {
+        pyo3::prepare_freethreaded_python();
+        Python::with_gil(|py| {
+            let series = Series::Int(ConcreteInt {
+                items: vec![1, -2].into(),
+            });
+            assert_eq!(series.to_list(py).extract::<Vec<i64>>(py).unwrap(), [1, -2]);
+            let series = Series::Float(ConcreteFloat {
+                items: vec![0.1, f64::INFINITY].into(),
+            });
+            assert_eq!(
+                series.to_list(py).extract::<Vec<f64>>(py).unwrap(),
+                [0.1, f64::INFINITY]
+            );
+            let series = Series::String(ConcreteString {
+                items: ["a", ""].map(str::to_owned).to_vec().into(),
+            });
+            assert_eq!(
+                series.to_list(py).extract::<Vec<String>>(py).unwrap(),
+                ["a", ""]
+            );
+            let series = Series::Bool(ConcreteBool {
+                items: vec![true, false].into(),
+            });
+            let list = series.to_list(py).into_bound(py);
+            assert!(list.iter().all(|item| item.is_instance_of::<PyBool>()));
+            assert_eq!(list.extract::<Vec<bool>>().unwrap(), [true, false]);
+            let rebuilt = Series::create(list.into_any()).unwrap();
+            assert_eq!(rebuilt.__repr__(), series.__repr__());
+            let series = Series::Int(ConcreteInt {
+                items: vec![5].into(),
+            })
+            .take_or_null(&[None, Some(0)]);
+            assert_eq!(
+                series.to_list(py).extract::<Vec<Option<i64>>>(py).unwrap(),
+                [None, Some(5)]
+            );
+        });
+    }
+
+    #[test]
+    fn test_sort() {
+        let series = Series::Float(ConcreteFloat {
+            items: vec![2.5, f64::NAN, -1.0, 7.0].into(),
+        });
+        assert_eq!(
+            series.sort(true).__repr__(),
+            "FloatSeries([-1.0, 2.5, 7.0, NaN])"
+        );
+        assert_eq!(
+            series.sort(false).__repr__(),
+            "FloatSeries([7.0, 2.5, -1.0, NaN])"
+        );
+        assert_eq!(series.argsort(true).__repr__(), "IntSeries([2, 0, 3, 1])");
+        let series = Series::Bool(ConcreteBool {
+            items: vec![true, false, true].into(),
+        });
+        assert_eq!(
+            series.sort(true).__repr__(),
+            "BoolSeries([false, true, true])"
+        );
+        let series = Series::String(ConcreteString {
+            items: ["b", "a", "c"].map(str::to_owned).to_vec().into(),
+        })
+        .take_or_null(&[Some(0), None, Some(1), Some(2)]);
+        assert_eq!(
+            series.sort(false).__repr__(),
+            "StringSeries([\"c\", \"b\", \"a\", None])"
+        );
+        assert_eq!(series.argsort(true).__repr__(), "IntSeries([2, 0, 3, 1])");
+    }
+
+    #[test]
+    fn test_value_counts() {
+        let series = Series::String(ConcreteString {
+            items: ["b", "c", "a", "c", "b", "d"]
+                .map(str::to_owned)
+                .to_vec()
+                .into(),
+        });
+        let counts = series.value_counts();
+        assert_eq!(
+            counts.item["value"].__repr__(),
+            "StringSeries([\"b\", \"c\", \"a\", \"d\"])"
+        );
+        assert_eq!(counts.item["count"].__repr__(), "IntSeries([2, 2, 1, 1])");
+        let series = Series::Float(ConcreteFloat {
+            items: vec![f64::NAN, 1.5, f64::NAN, 0.5].into(),
+        })
+        .take_or_null(&[Some(0), Some(1), Some(2), Some(3), None]);
+        let counts = series.value_counts();
+        assert_eq!(
+            counts.item["value"].__repr__(),
+            "FloatSeries([NaN, 0.5, 1.5])"
+        );
+        assert_eq!(counts.item["count"].__repr__(), "IntSeries([2, 1, 1])");
+    }
+
+    #[test]
+    fn test_pow_and_mod() {
+        let series = Series::Int(ConcreteInt {
+            items: vec![-3, 2, 7].into(),
+        });
+        let squared = series.pow_op(Operand::Int(2)).unwrap();
+        assert_eq!(squared.__repr__(), "IntSeries([9, 4, 49])");
+        let halves = series.pow_op(Operand::Float(0.5)).unwrap();
+        assert_eq!(halves.len(), 3);
+        let rem = series.mod_op(Operand::Int(3)).unwrap();
+        assert_eq!(rem.__repr__(), "IntSeries([0, 2, 1])");
+
+        pyo3::prepare_freethreaded_python();
+        let err = series.mod_op(Operand::Int(0)).unwrap_err();
+        Python::with_gil(|py| assert!(err.is_instance_of::<PyZeroDivisionError>(py)));
+        let floats = Series::Float(ConcreteFloat {
+            items: vec![1.5, -1.5].into(),
+        });
+        let rem = floats.mod_op(Operand::Float(1.0)).unwrap();
+        assert_eq!(rem.__repr__(), "FloatSeries([0.5, 0.5])");
+    }
+
+    #[test]
+    fn test_to_var_shares_buffer() {
+        let series = Series::Float(ConcreteFloat {
+            items: vec![0.5, 1.5].into(),
+        });
+        let Series::Float(ConcreteFloat { items }) = &series else {
+            unreachable!()
+        };
+        match series.to_var().unwrap() {
+            exec::Var::FloatV(values) => assert!(Arc::ptr_eq(&values, &items.items)),
+            var => panic!("Unexpected {var:?}"),
+        }
+
+        // Writes after sharing copy rather than affecting the executor's view.
+        let mut copy = series.clone();
+        let var = copy.to_var().unwrap();
+        if let Series::Float(ConcreteFloat { items }) = &mut copy {
+            items[0] = 9.0;
+        }
+        assert_eq!(var.to_string(), "[0.5, 1.5]");
+        assert_eq!(copy.__repr__(), "FloatSeries([9.0, 1.5])");
+    }
+
+    #[test]
+    fn test_column_access_shares_buffer() {
+        let df = DataFrame {
+            item: IndexMap::from([(
+                "a".to_owned(),
+                Series::Int(ConcreteInt {
+                    items: (0..100_000).collect(),
+                }),
+            )]),
+        };
+        pyo3::prepare_freethreaded_python();
+        Python::with_gil(|py| {
+            let column = df
+                .__getitem__(py, "a".to_object(py).into_bound(py))
+                .unwrap();
+            match (column.extract::<Series>(py).unwrap(), &df.item["a"]) {
+                (Series::Int(ConcreteInt { items: a }), Series::Int(ConcreteInt { items: b })) => {
+                    assert!(Arc::ptr_eq(&a.items, &b.items))
+                }
+                _ => unreachable!(),
+            }
+        });
+    }
+
+    #[test]
+    fn test_records_round_trip() {
+        let df = DataFrame {
+            item: IndexMap::from([
+                (
+                    "id".to_owned(),
+                    Series::Int(ConcreteInt {
+                        items: vec![1, 2].into(),
+                    }),
+                ),
+                (
+                    "score".to_owned(),
+                    Series::Float(ConcreteFloat {
+                        items: vec![0.5, -1.0].into(),
+                    }),
+                ),
+                (
+                    "name".to_owned(),
+                    Series::String(ConcreteString {
+                        items: ["a", "b"].map(str::to_owned).to_vec().into(),
+                    }),
+                ),
+                (
+                    "flag".to_owned(),
+                    Series::Bool(ConcreteBool {
+                        items: vec![true, false].into(),
+                    }),
+                ),
+            ]),
+        };
+        pyo3::prepare_freethreaded_python();
+        Python::with_gil(|py| {
+            let records = df.to_records(py);
+            assert_eq!(
+                records[0].bind(py).repr().unwrap().to_string(),
+                "(1, 0.5, 'a', True)"
+            );
+            let records = records.into_iter().map(|r| r.into_bound(py).into_any());
+            let rebuilt = DataFrame::from_records(py, records.collect(), df.columns()).unwrap();
+            assert_eq!(rebuilt.columns(), df.columns());
+            for column in df.columns() {
+                assert_eq!(
+                    rebuilt.item[&column].__repr__(),
+                    df.item[&column].__repr__()
+                );
+            }
+
+            let dict = df.to_dict(py).unwrap().into_bound(py);
+            assert_eq!(
+                dict.repr().unwrap().to_string(),
+                "{'id': [1, 2], 'score': [0.5, -1.0], 'name': ['a', 'b'], 'flag': [True, False]}"
+            );
+            let rebuilt = DataFrame::create(dict).unwrap();
+            assert_eq!(rebuilt.__repr__(), df.__repr__());
+        });
+    }
+
+    #[test]
+    fn test_query() {
+        let df = DataFrame {
+            item: IndexMap::from([
+                (
+                    "price".to_owned(),
+                    Series::Float(ConcreteFloat {
+                        items: vec![1.5, 120.0, 80.0].into(),
+                    }),
+                ),
+                (
+                    "qty".to_owned(),
+                    Series::Int(ConcreteInt {
+                        items: vec![1, 2, 3].into(),
+                    }),
+                ),
+            ]),
+        };
+        let cheap = df.query(":price < 100").unwrap();
+        assert_eq!(cheap.item["qty"].__repr__(), "IntSeries([1, 3])");
+        let picked = df.query(":price > 50 && :qty < 3").unwrap();
+        assert_eq!(picked.item["qty"].__repr__(), "IntSeries([2])");
+        let picked = df.query(":qty * 2 < 3 || (:price > 100)").unwrap();
+        assert_eq!(picked.item["qty"].__repr__(), "IntSeries([1, 2])");
+
+        pyo3::prepare_freethreaded_python();
+        let Err(err) = df.query(":prcie < 100") else {
+            panic!("Misspelled column should be reported");
+        };
+        Python::with_gil(|py| {
+            assert!(err.is_instance_of::<PyKeyError>(py));
+            assert!(err
+                .value_bound(py)
+                .to_string()
+                .contains("query references unknown column :prcie"));
+        });
+    }
+
+    #[test]
+    fn test_options() {
+        pyo3::prepare_freethreaded_python();
+        Python::with_gil(|py| {
+            let saved = Options.save(py).unwrap();
+            Options.set_na_token("NA".to_owned()).unwrap();
+            Options.set_parallel_threshold(10);
+            assert_eq!(Options.na_token(), "NA");
+            assert_eq!(Options.parallel_threshold(), 10);
+            assert!(Options.set_na_token("N,A".to_owned()).is_err());
+            Options.restore(saved).unwrap();
+            assert_eq!(options(), Config::default());
+        });
+    }
+
+    #[test]
+    fn test_sort_values() {
+        let df = DataFrame {
+            item: IndexMap::from([
+                (
+                    "a".to_owned(),
+                    Series::Int(ConcreteInt {
+                        items: vec![2, 1, 2, 1, 2].into(),
+                    }),
+                ),
+                (
+                    "b".to_owned(),
+                    Series::Float(ConcreteFloat {
+                        items: vec![0.5, 3.0, 1.5, 3.0, f64::NAN].into(),
+                    }),
+                ),
+                (
+                    "id".to_owned(),
+                    Series::String(ConcreteString {
+                        items: ["p", "q", "r", "s", "t"].map(str::to_owned).to_vec().into(),
+                    }),
+                ),
+            ]),
+        };
+        let sorted = df
+            .sort_values(
+                OneOrMany::Many(vec!["a".to_owned(), "b".to_owned()]),
+                Some(OneOrMany::Many(vec![true, false])),
+            )
+            .unwrap();
+        assert_eq!(
+            sorted.item["id"].__repr__(),
+            "StringSeries([\"q\", \"s\", \"r\", \"p\", \"t\"])"
+        );
+        assert_eq!(sorted.item["a"].__repr__(), "IntSeries([1, 1, 2, 2, 2])");
+
+        for ascending in [true, false] {
+            let sorted = df
+                .sort_values(
+                    OneOrMany::One("b".to_owned()),
+                    Some(OneOrMany::One(ascending)),
+                )
+                .unwrap();
+            assert!(matches!(
+                &sorted.item["b"],
+                Series::Float(ConcreteFloat { items }) if items[4].is_nan()
+            ));
+        }
+        let flags = DataFrame {
+            item: IndexMap::from([(
+                "flag".to_owned(),
+                Series::Bool(ConcreteBool {
+                    items: vec![true, false, true].into(),
+                }),
+            )]),
+        };
+        let sorted = flags
+            .sort_values(OneOrMany::One("flag".to_owned()), None)
+            .unwrap();
+        assert_eq!(
+            sorted.item["flag"].__repr__(),
+            "BoolSeries([false, true, true])"
+        );
+    }
+
+    #[test]
+    fn test_info() {
+        let df = DataFrame {
+            item: IndexMap::from([
+                (
+                    "price".to_owned(),
+                    Series::Float(ConcreteFloat {
+                        items: vec![1.5, f64::NAN, 2.5].into(),
+                    }),
+                ),
+                (
+                    "qty".to_owned(),
+                    Series::Int(ConcreteInt {
+                        items: vec![1, 2, 3].into(),
+                    }),
+                ),
+                (
+                    "name".to_owned(),
+                    Series::String(ConcreteString {
+                        items: ["ab", "c", ""].map(str::to_owned).to_vec().into(),
+                    }),
+                ),
+            ]),
+        };
+        let string_bytes = 3 * std::mem::size_of::<String>() + 3;
+        assert_eq!(
+            df.info(),
+            format!(
+                "3 rows, 3 columns\n \
+                 #  Column  Non-Null Count  Dtype\n \
+                 0  price   2 non-null      Float\n \
+                 1  qty     3 non-null      Int\n \
+                 2  name    3 non-null      String\n\
+                 memory usage: {} bytes",
+                48 + string_bytes
+            )
+        );
+    }
+
+    #[test]
+    fn test_cumulative() {
+        let series = Series::Int(ConcreteInt {
+            items: vec![1, 2, 3, 4].into(),
+        });
+        assert_eq!(
+            series.cumprod().unwrap().__repr__(),
+            "IntSeries([1, 2, 6, 24])"
+        );
+        let three = Series::Int(ConcreteInt {
+            items: vec![1, 2, 3].into(),
+        });
+        assert_eq!(three.cumsum().unwrap().__repr__(), "IntSeries([1, 3, 6])");
+        let strings = Series::String(ConcreteString {
+            items: vec!["a".to_owned()].into(),
+        });
+        assert!(strings.cumsum().is_err());
+        let series = Series::Int(ConcreteInt {
+            items: vec![1, 3, 2, 5].into(),
+        });
+        assert_eq!(
+            series.cummax().unwrap().__repr__(),
+            "IntSeries([1, 3, 3, 5])"
+        );
+        assert_eq!(
+            series.cummin().unwrap().__repr__(),
+            "IntSeries([1, 1, 1, 1])"
+        );
+        let series = Series::Float(ConcreteFloat {
+            items: vec![2.5, f64::NAN, 0.5].into(),
+        });
+        assert_eq!(
+            series.cummin().unwrap().__repr__(),
+            "FloatSeries([2.5, 2.5, 0.5])"
+        );
+    }
+
+    #[test]
+    fn test_push() {
+        pyo3::prepare_freethreaded_python();
+        Python::with_gil(|py| {
+            let mut builder = SeriesBuilder::create(Series::Int(ConcreteInt {
+                items: vec![1].into(),
+            }));
+            for value in [2, 3, 4] {
+                builder.push(&value.to_object(py).into_bound(py)).unwrap();
+            }
+            assert_eq!(builder.finish().__repr__(), "IntSeries([1, 2, 3, 4])");
+            let err = builder.push(&2.5.to_object(py).into_bound(py)).unwrap_err();
+            assert!(err.is_instance_of::<PyValueError>(py));
+            let err = builder
+                .push(&true.to_object(py).into_bound(py))
+                .unwrap_err();
+            assert!(err.is_instance_of::<PyValueError>(py));
+            assert_eq!(builder.__len__(), 4);
+
+            let mut nullable = SeriesBuilder::create(
+                Series::Float(ConcreteFloat {
+                    items: vec![1.5].into(),
+                })
+                .take_or_null(&[None, Some(0)]),
+            );
+            nullable.push(&2.to_object(py).into_bound(py)).unwrap();
+            assert_eq!(
+                nullable.finish().__repr__(),
+                "FloatSeries([None, 1.5, 2.0])"
+            );
+        });
+    }
+
+    #[test]
+    fn test_argmin_argmax() {
+        let series = Series::Int(ConcreteInt {
+            items: vec![1, 5, 3, 5].into(),
+        });
+        assert_eq!(series.argmax().unwrap(), 1);
+        assert_eq!(series.argmin().unwrap(), 0);
+        let series = Series::Float(ConcreteFloat {
+            items: vec![f64::NAN, 2.0, -1.0, -1.0].into(),
+        });
+        assert_eq!(series.argmin().unwrap(), 2);
+        assert_eq!(series.argmax().unwrap(), 1);
+
+        pyo3::prepare_freethreaded_python();
+        let empty = Series::Int(ConcreteInt {
+            items: vec![].into(),
+        });
+        assert!(empty.argmax().is_err());
+    }
+
+    #[test]
+    fn test_comparison_operators() {
+        let ints = Series::Int(ConcreteInt {
+            items: vec![1, 2, 3].into(),
+        });
+        let floats = Series::Float(ConcreteFloat {
+            items: vec![1.5, 2.0, f64::NAN].into(),
+        });
+        let cases = [
+            (CompareOp::Lt, "BoolSeries([true, false, false])"),
+            (CompareOp::Le, "BoolSeries([true, true, false])"),
+            (CompareOp::Gt, "BoolSeries([false, false, false])"),
+            (CompareOp::Ge, "BoolSeries([false, true, false])"),
+            (CompareOp::Eq, "BoolSeries([false, true, false])"),
+            (CompareOp::Ne, "BoolSeries([true, false, true])"),
+        ];
+        for (op, expected) in cases {
+            let mask = ints
+                .compare_op(Operand::Series(floats.clone()), op)
+                .unwrap();
+            assert_eq!(mask.__repr__(), expected, "{op:?}");
+        }
+
+        pyo3::prepare_freethreaded_python();
+        let strings = Series::String(ConcreteString {
+            items: ["a", "b", "c"].map(str::to_owned).to_vec().into(),
+        });
+        let mask = strings
+            .compare_op(Operand::String("b".to_owned()), CompareOp::Ne)
+            .unwrap();
+        assert_eq!(mask.__repr__(), "BoolSeries([true, false, true])");
+        assert!(strings
+            .compare_op(Operand::String("b".to_owned()), CompareOp::Lt)
+            .is_err());
+        let short = Series::Int(ConcreteInt {
+            items: vec![1].into(),
+        });
+        assert!(ints
+            .compare_op(Operand::Series(short), CompareOp::Eq)
+            .is_err());
+    }
+
+    #[test]
+    fn test_sum() {
+        pyo3::prepare_freethreaded_python();
+        Python::with_gil(|py| {
+            let ints = Series::Int(ConcreteInt {
+                items: vec![1, 2, 3].into(),
+            });
+            assert_eq!(ints.sum(py).unwrap().extract::<i64>(py).unwrap(), 6);
+            let empty = Series::Int(ConcreteInt {
+                items: vec![].into(),
+            });
+            assert_eq!(
+                empty.sum(py).unwrap().bind(py).repr().unwrap().to_string(),
+                "0"
+            );
+            let empty = Series::Float(ConcreteFloat {
+                items: vec![].into(),
+            });
+            assert_eq!(
+                empty.sum(py).unwrap().bind(py).repr().unwrap().to_string(),
+                "0.0"
+            );
+            let strings = Series::String(ConcreteString {
+                items: vec!["a".to_owned()].into(),
+            });
+            assert!(strings.sum(py).is_err());
+        });
+    }
+
+    #[test]
+    fn test_mean() {
+        pyo3::prepare_freethreaded_python();
+        let ints = Series::Int(ConcreteInt {
+            items: vec![1, 2, 3, 4].into(),
+        });
+        assert_eq!(ints.mean().unwrap(), 2.5);
+        let floats = Series::Float(ConcreteFloat {
+            items: vec![0.5, 1.5].into(),
+        });
+        assert_eq!(floats.mean().unwrap(), 1.0);
+        let empty = Series::Float(ConcreteFloat {
+            items: vec![].into(),
+        });
+        assert!(empty.mean().is_err());
+        let bools = Series::Bool(ConcreteBool {
+            items: vec![true].into(),
+        });
+        assert!(bools.mean().is_err());
+    }
+
+    #[test]
+    fn test_quantile() {
+        pyo3::prepare_freethreaded_python();
+        let odd = Series::Int(ConcreteInt {
+            items: vec![5, 1, 3].into(),
+        });
+        assert_eq!(odd.median().unwrap(), 3.0);
+        let even = Series::Float(ConcreteFloat {
+            items: vec![4.0, f64::NAN, 1.0, 3.0, 2.0].into(),
+        });
+        assert_eq!(even.median().unwrap(), 2.5);
+        assert_eq!(even.quantile(0.0).unwrap(), 1.0);
+        assert_eq!(even.quantile(0.25).unwrap(), 1.75);
+        assert_eq!(even.quantile(1.0).unwrap(), 4.0);
+        let infinite = Series::Float(ConcreteFloat {
+            items: vec![f64::INFINITY, f64::INFINITY].into(),
+        });
+        assert_eq!(infinite.median().unwrap(), f64::INFINITY);
+        let nulls = Series::Int(ConcreteInt {
+            items: vec![10, 20].into(),
+        })
+        .take_or_null(&[Some(0), None, Some(1), None]);
+        assert_eq!(nulls.median().unwrap(), 15.0);
+        for q in [-0.1, 1.5, f64::NAN] {
+            assert!(odd.quantile(q).is_err(), "{q}");
+        }
+        let empty = Series::Float(ConcreteFloat {
+            items: vec![f64::NAN].into(),
+        });
+        assert!(empty.median().is_err());
+        let strings = Series::String(ConcreteString {
+            items: vec!["a".to_owned()].into(),
+        });
+        assert!(strings.median().is_err());
+    }
+
+    #[test]
+    fn test_min_max() {
+        pyo3::prepare_freethreaded_python();
+        Python::with_gil(|py| {
+            let ints = Series::Int(ConcreteInt {
+                items: vec![3, -1, 7].into(),
+            });
+            assert_eq!(ints.min(py).unwrap().extract::<i64>(py).unwrap(), -1);
+            assert_eq!(ints.max(py).unwrap().extract::<i64>(py).unwrap(), 7);
+            let floats = Series::Float(ConcreteFloat {
+                items: vec![f64::NAN, 2.5, 1.0].into(),
+            });
+            assert_eq!(floats.max(py).unwrap().extract::<f64>(py).unwrap(), 2.5);
+            let nans = Series::Float(ConcreteFloat {
+                items: vec![f64::NAN, f64::NAN].into(),
+            });
+            assert!(nans.min(py).unwrap().extract::<f64>(py).unwrap().is_nan());
+            let strings = Series::String(ConcreteString {
+                items: vec!["pear".to_owned(), "apple".to_owned()].into(),
+            });
+            assert_eq!(
+                strings.min(py).unwrap().extract::<String>(py).unwrap(),
+                "apple"
+            );
+            let empty = Series::Int(ConcreteInt {
+                items: vec![].into(),
+            });
+            assert!(empty.max(py).is_err());
+            let bools = Series::Bool(ConcreteBool {
+                items: vec![true].into(),
+            });
+            assert!(bools.min(py).is_err());
+        });
+    }
+
+    #[test]
+    fn test_var_std() {
+        pyo3::prepare_freethreaded_python();
+        let ints = Series::Int(ConcreteInt {
+            items: vec![2, 4, 4, 4, 5, 5, 7, 9].into(),
+        });
+        assert_eq!(ints.var(0).unwrap(), 4.0);
+        assert_eq!(ints.std(0).unwrap(), 2.0);
+        assert!((ints.var(1).unwrap() - 32.0 / 7.0).abs() < 1e-12);
+        let one = Series::Float(ConcreteFloat {
+            items: vec![1.5].into(),
+        });
+        assert_eq!(one.var(0).unwrap(), 0.0);
+        assert!(one.var(1).is_err());
+        let strings = Series::String(ConcreteString {
+            items: vec!["a".to_owned(), "b".to_owned()].into(),
+        });
+        assert!(strings.std(1).is_err());
+    }
+
+    #[test]
+    fn test_pickle_round_trip() {
+        pyo3::prepare_freethreaded_python();
+        Python::with_gil(|py| {
+            let module = PyModule::new_bound(py, "dfrs").unwrap();
+            module.add_class::<DataFrame>().unwrap();
+            py.import_bound("sys")
+                .unwrap()
+                .getattr("modules")
+                .unwrap()
+                .set_item("dfrs", module)
+                .unwrap();
+            let df = DataFrame {
+                item: IndexMap::from([
+                    (
+                        "z".to_owned(),
+                        Series::Float(ConcreteFloat {
+                            items: vec![0.1 + 0.2, f64::NAN, -1e-300].into(),
+                        }),
+                    ),
+                    (
+                        "a".to_owned(),
+                        Series::Int(ConcreteInt {
+                            items: vec![1, 0, i64::MIN].into(),
+                        })
+                        .take_or_null(&[Some(0), None, Some(2)]),
+                    ),
+                    (
+                        "s".to_owned(),
+                        Series::String(ConcreteString {
+                            items: ["x", "", "ü"].map(str::to_owned).to_vec().into(),
+                        }),
+                    ),
+                    (
+                        "b".to_owned(),
+                        Series::Bool(ConcreteBool {
+                            items: vec![true, false, true].into(),
+                        }),
+                    ),
+                ]),
+            };
+            let pickle = py.import_bound("pickle").unwrap();
+            let bytes = pickle.call_method1("dumps", (df.clone(),)).unwrap();
+            let loaded: DataFrame = pickle
+                .call_method1("loads", (bytes,))
+                .unwrap()
+                .extract()
+                .unwrap();
+            assert_eq!(loaded.__repr__(), df.__repr__());
+            assert_eq!(loaded.columns(), ["z", "a", "s", "b"]);
+        });
+    }
+
+    #[test]
+    fn test_frame_equality() {
+        let ints = |items: Vec<i64>| {
+            Series::Int(ConcreteInt {
+                items: items.into(),
+            })
+        };
+        let floats = |items: Vec<f64>| {
+            Series::Float(ConcreteFloat {
+                items: items.into(),
+            })
+        };
+        let df = DataFrame {
+            item: IndexMap::from([
+                ("a".to_owned(), ints(vec![1, 2])),
+                ("b".to_owned(), floats(vec![f64::NAN, 0.5])),
+            ]),
+        };
+        let reordered = DataFrame {
+            item: IndexMap::from([
+                ("b".to_owned(), floats(vec![f64::NAN, 0.5])),
+                ("a".to_owned(), ints(vec![1, 2])),
+            ]),
+        };
+        assert!(df.__eq__(&reordered));
+        for (name, series) in [
+            ("a", floats(vec![1.0, 2.0])),
+            ("a", ints(vec![1, 3])),
+            ("a", ints(vec![1, 2, 3])),
+            ("a", ints(vec![1, 2]).take_or_null(&[Some(0), None])),
+            ("b", floats(vec![f64::NAN, 0.5 + f64::EPSILON])),
+            ("c", ints(vec![1, 2])),
+        ] {
+            let mut other = df.clone();
+            other.item.insert(name.to_owned(), series);
+            assert!(!df.__eq__(&other), "{name}: {:?}", other.item);
+        }
+        let nulls = ints(vec![1, 2]).take_or_null(&[None, Some(1)]);
+        let placeholder = ints(vec![7, 2]).with_validity(nulls.validity().cloned());
+        assert!(nulls.equals(&placeholder));
+    }
+
+    #[test]
+    fn test_assign() {
+        pyo3::prepare_freethreaded_python();
+        Python::with_gil(|py| {
+            let df = DataFrame {
+                item: IndexMap::from([(
+                    "a".to_owned(),
+                    Series::Int(ConcreteInt {
+                        items: vec![1, 2].into(),
+                    }),
+                )]),
+            };
+            let doubled = df.item["a"]
+                .__mul__(2i64.into_py(py).into_bound(py))
+                .unwrap();
+            let assigned = df
+                .assign("b".to_owned(), doubled.into_py(py).into_bound(py))
+                .unwrap()
+                .assign("a".to_owned(), PyList::new_bound(py, ["x", "y"]).into_any())
+                .unwrap();
+            assert_eq!(assigned.columns(), ["a", "b"]);
+            assert_eq!(
+                assigned.item["a"].__repr__(),
+                "StringSeries([\"x\", \"y\"])"
+            );
+            assert_eq!(assigned.item["b"].__repr__(), "IntSeries([2, 4])");
+            assert_eq!(df.columns(), ["a"]);
+            assert_eq!(df.item["a"].__repr__(), "IntSeries([1, 2])");
+            let err = df
+                .assign("c".to_owned(), PyList::new_bound(py, [1]).into_any())
+                .err()
+                .unwrap();
+            assert!(err.is_instance_of::<PyValueError>(py));
+        });
+    }
+
+    #[test]
+    fn test_drop() {
+        pyo3::prepare_freethreaded_python();
+        let column = || {
+            Series::Int(ConcreteInt {
+                items: vec![1].into(),
+            })
+        };
+        let df = DataFrame {
+            item: IndexMap::from([
+                ("a".to_owned(), column()),
+                ("b".to_owned(), column()),
+                ("c".to_owned(), column()),
+            ]),
+        };
+        let dropped = df.drop(vec!["b".to_owned()], "raise").unwrap();
+        assert_eq!(dropped.columns(), ["a", "c"]);
+        assert!(df.__contains__("b"));
+        assert!(!dropped.__contains__("b"));
+        assert_eq!(df.columns(), ["a", "b", "c"]);
+        assert!(df.drop(vec!["d".to_owned()], "raise").is_err());
+        let dropped = df
+            .drop(vec!["a".to_owned(), "d".to_owned()], "ignore")
+            .unwrap();
+        assert_eq!(dropped.columns(), ["b", "c"]);
+    }
+
+    #[test]
+    fn test_rename() {
+        pyo3::prepare_freethreaded_python();
+        Python::with_gil(|py| {
+            let column = |v| {
+                Series::Int(ConcreteInt {
+                    items: vec![v].into(),
+                })
+            };
+            let mut df = DataFrame {
+                item: IndexMap::from([("a".to_owned(), column(1)), ("c".to_owned(), column(2))]),
+            };
+            let mapping = |pairs: &[(&str, &str)]| pairs.into_py_dict_bound(py);
+            df.rename(mapping(&[("a", "b")])).unwrap();
+            let b = df
+                .__getitem__(py, "b".to_object(py).into_bound(py))
+                .unwrap();
+            assert_eq!(
+                b.extract::<Series>(py).unwrap().__repr__(),
+                "IntSeries([1])"
+            );
+            assert_eq!(df.columns(), ["b", "c"]);
+
+            assert!(df.rename(mapping(&[("a", "d")])).is_err());
+            assert!(df.rename(mapping(&[("b", "c")])).is_err());
+            assert!(df.rename(mapping(&[("b", "d"), ("c", "d")])).is_err());
+            // Swapping names is fine since neither target collides after the renames.
+            df.rename(mapping(&[("b", "c"), ("c", "b")])).unwrap();
+            assert_eq!(df.item["c"].__repr__(), "IntSeries([1])");
+        });
+    }
+
+    #[test]
+    fn test_column_order() {
+        pyo3::prepare_freethreaded_python();
+        Python::with_gil(|py| {
+            let csv = "z,a,m\n1,2,3\n";
+            let mut df =
+                DataFrame::from_csv_reader(BufReader::new(csv.as_bytes()), &ReadOptions::default())
+                    .unwrap();
+            assert_eq!(df.columns(), ["z", "a", "m"]);
+            df.__setitem__(
+                PyString::new_bound(py, "b"),
+                PyList::new_bound(py, [4]).into_any(),
+            )
+            .unwrap();
+            df.rename([("z", "y")].into_py_dict_bound(py)).unwrap();
+            assert_eq!(df.columns(), ["y", "a", "m", "b"]);
+            let df = df.drop(vec!["a".to_owned()], "raise").unwrap();
+            assert_eq!(
+                df.__repr__(),
+                "DataFrame({\"y\": IntSeries([1]), \"m\": IntSeries([3]), \"b\": IntSeries([4])})"
+            );
+        });
+    }
+
+    #[test]
+    fn test_shape() {
+        let column = || {
+            Series::Int(ConcreteInt {
+                items: vec![1, 2, 3, 4].into(),
+            })
+        };
+        let df = DataFrame {
+            item: IndexMap::from([
+                ("a".to_owned(), column()),
+                ("b".to_owned(), column()),
+                ("c".to_owned(), column()),
+            ]),
+        };
+        assert_eq!(df.shape(), (4, 3));
+        let empty = DataFrame {
+            item: IndexMap::new(),
+        };
+        assert_eq!(empty.shape(), (0, 0));
+    }
+
+    #[test]
+    fn test_rows() {
+        pyo3::prepare_freethreaded_python();
+        Python::with_gil(|py| {
+            let df = DataFrame {
+                item: IndexMap::from([
+                    (
+                        "a".to_owned(),
+                        Series::Int(ConcreteInt {
+                            items: vec![1, 2].into(),
+                        }),
+                    ),
+                    (
+                        "b".to_owned(),
+                        Series::String(ConcreteString {
+                            items: vec!["x".to_owned(), "y".to_owned()].into(),
+                        }),
+                    ),
+                ]),
+            };
+            let mut rows = df.__iter__();
+            let mut seen = vec![];
+            while let Some(row) = rows.__next__(py).unwrap() {
+                seen.push(row.repr().unwrap().to_string());
+            }
+            assert_eq!(seen, ["{'a': 1, 'b': 'x'}", "{'a': 2, 'b': 'y'}"]);
+
+            let row = |i: isize| df.__getitem__(py, i.to_object(py).into_bound(py));
+            let last = row(-1).unwrap();
+            assert_eq!(
+                last.bind(py).repr().unwrap().to_string(),
+                "{'a': 2, 'b': 'y'}"
+            );
+            assert!(row(2).is_err());
+        });
+    }
+
+    #[test]
+    fn test_column_selection() {
+        pyo3::prepare_freethreaded_python();
+        Python::with_gil(|py| {
+            let column = |v| {
+                Series::Int(ConcreteInt {
+                    items: vec![v].into(),
+                })
+            };
+            let df = DataFrame {
+                item: IndexMap::from([
+                    ("a".to_owned(), column(1)),
+                    ("b".to_owned(), column(2)),
+                    ("c".to_owned(), column(3)),
+                ]),
+            };
+            let select = |names: &[&str]| df.__getitem__(py, names.to_object(py).into_bound(py));
+            let selected = select(&["c", "a"])
+                .unwrap()
+                .extract::<DataFrame>(py)
+                .unwrap();
+            assert_eq!(selected.columns(), ["c", "a"]);
+            assert_eq!(selected.item["c"].__repr__(), "IntSeries([3])");
+            let Err(err) = select(&["a", "d"]) else {
+                panic!("selecting a missing column should fail");
+            };
+            assert!(err.is_instance_of::<PyKeyError>(py));
+            let single = df
+                .__getitem__(py, "b".to_object(py).into_bound(py))
+                .unwrap();
+            assert!(single.extract::<Series>(py).is_ok());
+        });
+    }
+
+    #[test]
+    fn test_groupby_sum() {
+        pyo3::prepare_freethreaded_python();
+        let df = DataFrame {
+            item: IndexMap::from([
+                (
+                    "city".to_owned(),
+                    Series::String(ConcreteString {
+                        items: ["b", "a", "b"].map(str::to_owned).to_vec().into(),
+                    }),
+                ),
+                (
+                    "sales".to_owned(),
+                    Series::Float(ConcreteFloat {
+                        items: vec![1.5, 2.0, 3.0].into(),
+                    }),
+                ),
+            ]),
+        };
+        let sums = df
+            .groupby_sum("city".to_owned(), "sales".to_owned())
+            .unwrap();
+        assert_eq!(
+            sums.__repr__(),
+            "DataFrame({\"city\": StringSeries([\"a\", \"b\"]), \"sales\": FloatSeries([2.0, 4.5])})"
+        );
+        assert!(df
+            .groupby_sum("sales".to_owned(), "city".to_owned())
+            .is_err());
+        assert!(df
+            .groupby_sum("city".to_owned(), "city".to_owned())
+            .is_err());
+
+        let overflowing = DataFrame {
+            item: IndexMap::from([
+                (
+                    "k".to_owned(),
+                    Series::String(ConcreteString {
+                        items: ["a", "b", "b"].map(str::to_owned).to_vec().into(),
+                    }),
+                ),
+                (
+                    "v".to_owned(),
+                    Series::Int(ConcreteInt {
+                        items: vec![1, i64::MAX, 1].into(),
+                    }),
+                ),
+            ]),
+        };
+        let Err(err) = overflowing.groupby_sum("k".to_owned(), "v".to_owned()) else {
+            panic!("expected an overflow");
+        };
+        Python::with_gil(|py| {
+            assert!(err.is_instance_of::<PyOverflowError>(py));
+            assert_eq!(
+                err.value_bound(py).to_string(),
+                "Sum overflows Int in group \"b\""
+            );
+        });
+    }
+
+    #[test]
+    fn test_head_tail() {
+        let df = DataFrame {
+            item: IndexMap::from([
+                (
+                    "a".to_owned(),
+                    Series::Int(ConcreteInt {
+                        items: (0..8).collect(),
+                    }),
+                ),
+                (
+                    "b".to_owned(),
+                    Series::Float(ConcreteFloat {
+                        items: (0..8).map(|i| i as f64).collect(),
+                    }),
+                ),
+            ]),
+        };
+        assert_eq!(
+            df.head(5).item["a"].__repr__(),
+            "IntSeries([0, 1, 2, 3, 4])"
+        );
+        assert_eq!(df.tail(2).item["b"].__repr__(), "FloatSeries([6.0, 7.0])");
+        assert_eq!(df.head(20).__len__(), 8);
+        assert_eq!(df.tail(20).__len__(), 8);
+        assert_eq!(df.tail(0).item["a"].__repr__(), "IntSeries([])");
+    }
+
+    #[test]
+    fn test_mask_filtering() {
+        pyo3::prepare_freethreaded_python();
+        Python::with_gil(|py| {
+            let df = DataFrame {
+                item: IndexMap::from([
+                    (
+                        "a".to_owned(),
+                        Series::Int(ConcreteInt {
+                            items: vec![1, 2, 3].into(),
+                        }),
+                    ),
+                    (
+                        "b".to_owned(),
+                        Series::String(ConcreteString {
+                            items: vec!["x".to_owned(), "y".to_owned(), "z".to_owned()].into(),
+                        }),
+                    ),
+                ]),
+            };
+            let mask = |items: Vec<bool>| {
+                Series::Bool(ConcreteBool {
+                    items: items.into(),
+                })
+                .into_py(py)
+                .into_bound(py)
+            };
+            let filtered = df.__getitem__(py, mask(vec![true, false, true])).unwrap();
+            let filtered = filtered.extract::<DataFrame>(py).unwrap();
+            assert_eq!(filtered.item["a"].__repr__(), "IntSeries([1, 3])");
+            assert_eq!(
+                filtered.item["b"].__repr__(),
+                "StringSeries([\"x\", \"z\"])"
+            );
+            assert!(df.__getitem__(py, mask(vec![true])).is_err());
+        });
+    }
+
+    #[test]
+    fn test_series_len() {
+        let series = [
+            Series::Int(ConcreteInt {
+                items: vec![1, 2, 3].into(),
+            }),
+            Series::Float(ConcreteFloat {
+                items: vec![0.5].into(),
+            }),
+            Series::String(ConcreteString {
+                items: vec!["a".to_owned(), "b".to_owned()].into(),
+            }),
+            Series::Bool(ConcreteBool {
+                items: vec![].into(),
+            }),
+        ];
+        let lengths: Vec<usize> = series.iter().map(|s| s.__len__()).collect();
+        assert_eq!(lengths, [3, 1, 2, 0]);
+    }
+
+    #[test]
+    fn test_getitem() {
+        pyo3::prepare_freethreaded_python();
+        Python::with_gil(|py| {
+            let series = Series::String(ConcreteString {
+                items: vec!["a".to_owned(), "b".to_owned(), "c".to_owned()].into(),
+            });
+            let item = |i: isize| {
+                series
+                    .__getitem__(py, i.to_object(py).into_bound(py))
+                    .map(|v| v.extract::<String>(py).unwrap())
+            };
+            assert_eq!(item(0).unwrap(), "a");
+            assert_eq!(item(-1).unwrap(), "c");
+            assert_eq!(item(-3).unwrap(), "a");
+            assert!(item(3).is_err());
+            assert!(item(-4).is_err());
+
+            let slice = |start, stop, step| {
+                let key = PySlice::new_bound(py, start, stop, step).into_any();
+                let sliced = series.__getitem__(py, key).unwrap();
+                sliced.extract::<Series>(py).unwrap().__repr__()
+            };
+            assert_eq!(slice(1, 3, 1), "StringSeries([\"b\", \"c\"])");
+            assert_eq!(slice(-1, -4, -2), "StringSeries([\"c\", \"a\"])");
+            assert_eq!(slice(2, 1, 1), "StringSeries([])");
+        });
+    }
+
+    #[test]
+    fn test_between() {
+        let series = Series::Int(ConcreteInt {
+            items: vec![1, 2, 3, 4, 5].into(),
+        });
+        let both = series
+            .between_mask(Operand::Int(2), Operand::Int(4), true, true)
+            .unwrap();
+        assert_eq!(
+            both.__repr__(),
+            "BoolSeries([false, true, true, true, false])"
+        );
+        let neither = series
+            .between_mask(Operand::Int(2), Operand::Int(4), false, false)
+            .unwrap();
+        assert_eq!(
+            neither.__repr__(),
+            "BoolSeries([false, false, true, false, false])"
+        );
+        let strings = Series::String(ConcreteString {
+            items: vec!["apple".to_owned(), "kiwi".to_owned(), "pear".to_owned()].into(),
+        });
+        let mask = strings
+            .between_mask(
+                Operand::String("b".to_owned()),
+                Operand::String("pear".to_owned()),
+                true,
+                false,
+            )
+            .unwrap();
+        assert_eq!(mask.__repr__(), "BoolSeries([false, true, false])");
+    }
+
+    #[test]
+    fn test_series_sub() {
+        let ints = Series::Int(ConcreteInt {
+            items: vec![5, 7, 9].into(),
+        });
+        let floats = Series::Float(ConcreteFloat {
+            items: vec![0.5, 1.0, 1.5].into(),
+        });
+        let diff = (ints.clone() - floats).unwrap();
+        assert_eq!(diff.__repr__(), "FloatSeries([4.5, 6.0, 7.5])");
+        let diff = (ints.clone() - ints.clone()).unwrap();
+        assert_eq!(diff.__repr__(), "IntSeries([0, 0, 0])");
+
+        pyo3::prepare_freethreaded_python();
+        let strings = Series::String(ConcreteString {
+            items: ["a", "b", "c"].map(str::to_owned).to_vec().into(),
+        });
+        assert!((strings.clone() - strings).is_err());
+        let short = Series::Int(ConcreteInt {
+            items: vec![1].into(),
+        });
+        assert!((ints - short).is_err());
+    }
+
+    #[test]
+    fn test_string_concatenation() {
+        let lhs = Series::String(ConcreteString {
+            items: ["a", "b"].map(str::to_owned).to_vec().into(),
+        });
+        let rhs = Series::String(ConcreteString {
+            items: ["x", "y"].map(str::to_owned).to_vec().into(),
+        });
+        let joined = (lhs.clone() + rhs.clone()).unwrap();
+        assert_eq!(joined.__repr__(), "StringSeries([\"ax\", \"by\"])");
+        assert_eq!(
+            lhs.str_cat(rhs, "-").unwrap().__repr__(),
+            "StringSeries([\"a-x\", \"b-y\"])"
+        );
+
+        pyo3::prepare_freethreaded_python();
+        let short = Series::String(ConcreteString {
+            items: vec!["z".to_owned()].into(),
+        });
+        assert!((lhs.clone() + short.clone()).is_err());
+        assert!(lhs.str_cat(short, "").is_err());
+        let ints = Series::Int(ConcreteInt {
+            items: vec![1, 2].into(),
+        });
+        assert!(lhs.str_cat(ints, "").is_err());
+    }
+
+    #[test]
+    fn test_string_methods() {
+        let series = Series::String(ConcreteString {
+            items: ["Straße", "apple", "Apricot"]
+                .map(str::to_owned)
+                .to_vec()
+                .into(),
+        });
+        assert_eq!(
+            series.str_upper().unwrap().__repr__(),
+            "StringSeries([\"STRASSE\", \"APPLE\", \"APRICOT\"])"
+        );
+        assert_eq!(
+            series.str_lower().unwrap().__repr__(),
+            "StringSeries([\"straße\", \"apple\", \"apricot\"])"
+        );
+        assert_eq!(
+            series.str_contains("pp").unwrap().__repr__(),
+            "BoolSeries([false, true, false])"
+        );
+        assert_eq!(
+            series.str_startswith("Ap").unwrap().__repr__(),
+            "BoolSeries([false, false, true])"
+        );
+        assert_eq!(series.str_len().unwrap().__repr__(), "IntSeries([6, 5, 7])");
+
+        pyo3::prepare_freethreaded_python();
+        let ints = Series::Int(ConcreteInt {
+            items: vec![1].into(),
+        });
+        let err = ints.str_len().unwrap_err();
+        Python::with_gil(|py| {
+            assert!(err.is_instance_of::<PyValueError>(py));
+            assert_eq!(
+                err.value_bound(py).to_string(),
+                "str_len is undefined for Int Series"
+            );
+        });
+    }
+
+    #[test]
+    fn test_mul_and_div() {
+        let ints = Series::Int(ConcreteInt {
+            items: vec![3, 0, -2].into(),
+        });
+        let product = ints.mul_op(Operand::Series(ints.clone())).unwrap();
+        assert_eq!(product.__repr__(), "IntSeries([9, 0, 4])");
+        let product = ints.mul_op(Operand::Float(0.5)).unwrap();
+        assert_eq!(product.__repr__(), "FloatSeries([1.5, 0.0, -1.0])");
+
+        let quotient = ints.div_op(Operand::Int(2)).unwrap();
+        assert_eq!(quotient.__repr__(), "FloatSeries([1.5, 0.0, -1.0])");
+        let quotient = ints.div_op(Operand::Series(ints.clone())).unwrap();
+        assert_eq!(quotient.__repr__(), "FloatSeries([1.0, NaN, 1.0])");
+        let quotient = ints.div_op(Operand::Int(0)).unwrap();
+        assert_eq!(quotient.__repr__(), "FloatSeries([inf, NaN, -inf])");
+
+        pyo3::prepare_freethreaded_python();
+        let strings = Series::String(ConcreteString {
+            items: ["a", "b", "c"].map(str::to_owned).to_vec().into(),
+        });
+        assert!(strings.mul_op(Operand::Int(2)).is_err());
+        assert!(ints.div_op(Operand::Series(strings)).is_err());
+    }
+
+    #[test]
+    fn test_scalar_broadcast() {
+        let ints = Series::Int(ConcreteInt {
+            items: vec![1, 2, 3].into(),
+        });
+        assert_eq!(
+            ints.add_op(Operand::Int(5)).unwrap().__repr__(),
+            "IntSeries([6, 7, 8])"
+        );
+        assert_eq!(
+            ints.add_op(Operand::Float(0.5)).unwrap().__repr__(),
+            "FloatSeries([1.5, 2.5, 3.5])"
+        );
+        assert_eq!(
+            ints.sub_op(Operand::Float(1.0)).unwrap().__repr__(),
+            "FloatSeries([0.0, 1.0, 2.0])"
+        );
+        assert_eq!(
+            ints.mul_op(Operand::Float(2.0)).unwrap().__repr__(),
+            "FloatSeries([2.0, 4.0, 6.0])"
+        );
+        assert_eq!(
+            ints.div_op(Operand::Int(4)).unwrap().__repr__(),
+            "FloatSeries([0.25, 0.5, 0.75])"
+        );
+
+        pyo3::prepare_freethreaded_python();
+        for op in [
+            Series::add_op,
+            Series::sub_op,
+            Series::mul_op,
+            Series::div_op,
+        ] {
+            assert!(op(&ints, Operand::String("a".to_owned())).is_err());
+        }
+    }
+
+    #[test]
+    fn test_checked_sub() {
+        let series = Series::Int(ConcreteInt {
+            items: vec![5, i64::MIN + 1, 0].into(),
+        });
+        let diff = series.sub_op(Operand::Int(1)).unwrap();
+        assert_eq!(diff.__repr__(), format!("IntSeries([4, {}, -1])", i64::MIN));
+
+        let nullable = Series::Int(ConcreteInt {
+            items: vec![5, 0].into(),
+        })
+        .take_or_null(&[Some(0), None]);
+        let rhs = Series::Int(ConcreteInt {
+            items: vec![1, i64::MIN].into(),
+        });
+        let diff = nullable.sub_op(Operand::Series(rhs)).unwrap();
+        assert_eq!(diff.__repr__(), "IntSeries([4, None])");
+
+        pyo3::prepare_freethreaded_python();
+        let err = series.sub_op(Operand::Int(2)).unwrap_err();
+        Python::with_gil(|py| {
+            assert!(err.is_instance_of::<PyOverflowError>(py));
+            assert!(err.value_bound(py).to_string().contains("index 1"));
+        });
+    }
+
+    #[test]
+    fn test_checked_add() {
+        let series = Series::Int(ConcreteInt {
+            items: vec![1, i64::MAX].into(),
+        });
+        let ones = Series::Int(ConcreteInt {
+            items: vec![1, 1].into(),
+        });
+        let shifted = Series::Int(ConcreteInt {
+            items: vec![i64::MAX - 1, 0].into(),
+        });
+        assert_eq!(
+            series.add_op(Operand::Series(shifted)).unwrap().__repr__(),
+            format!("IntSeries([{}, {}])", i64::MAX, i64::MAX)
+        );
+
+        pyo3::prepare_freethreaded_python();
+        let via_op = series.add_op(Operand::Series(ones.clone())).unwrap_err();
+        let via_add = (series + ones).unwrap_err();
+        Python::with_gil(|py| {
+            for err in [via_op, via_add] {
+                assert!(err.is_instance_of::<PyOverflowError>(py));
+                assert!(err.value_bound(py).to_string().contains("index 1"));
+            }
+        });
+    }
+
+    #[test]
+    fn test_astype() {
+        let floats = Series::Float(ConcreteFloat {
+            items: vec![1.5, -2.7, 3.0].into(),
+        });
+        assert_eq!(
+            floats.astype("int").unwrap().__repr__(),
+            "IntSeries([1, -2, 3])"
+        );
+        assert_eq!(
+            floats.astype("string").unwrap().__repr__(),
+            "StringSeries([\"1.5\", \"-2.7\", \"3.0\"])"
+        );
+        let ints = Series::Int(ConcreteInt {
+            items: vec![1, 2].into(),
+        });
+        assert_eq!(
+            ints.astype("float").unwrap().__repr__(),
+            "FloatSeries([1.0, 2.0])"
+        );
+        match (ints.astype("int").unwrap(), &ints) {
+            (Series::Int(ConcreteInt { items: a }), Series::Int(ConcreteInt { items: b })) => {
+                assert!(Arc::ptr_eq(&a.items, &b.items))
+            }
+            _ => unreachable!(),
+        }
+        let strings = Series::String(ConcreteString {
+            items: vec!["4".to_owned(), "x".to_owned()].into(),
+        });
+        pyo3::prepare_freethreaded_python();
+        Python::with_gil(|py| {
+            let err = strings.astype("int").unwrap_err();
+            assert_eq!(err.value_bound(py).to_string(), "\"x\" is not a valid Int");
+            assert!(floats.astype("bool").is_err());
+        });
+        let with_null = strings.take_or_null(&[Some(0), None]);
+        assert_eq!(
+            with_null.astype("float").unwrap().__repr__(),
+            "FloatSeries([4.0, None])"
+        );
+    }
+
+    #[test]
+    fn test_apply() {
+        let series = Series::Int(ConcreteInt {
+            items: vec![1, 2, 3].into(),
+        });
+        pyo3::prepare_freethreaded_python();
+        Python::with_gil(|py| {
+            let apply = |source: &str| {
+                let func = py.eval_bound(source, None, None).unwrap();
+                series.apply(py, func)
+            };
+            assert_eq!(
+                apply("lambda x: x * 2").unwrap().__repr__(),
+                "IntSeries([2, 4, 6])"
+            );
+            assert_eq!(
+                apply("lambda x: x / 2").unwrap().__repr__(),
+                "FloatSeries([0.5, 1.0, 1.5])"
+            );
+            assert_eq!(
+                apply("lambda x: x > 1").unwrap().__repr__(),
+                "BoolSeries([false, true, true])"
+            );
+            let err = apply("lambda x: 1 // (x - 2)").unwrap_err();
+            assert!(err.is_instance_of::<PyZeroDivisionError>(py));
+        });
+    }
+
+    #[test]
+    fn test_isin() {
+        pyo3::prepare_freethreaded_python();
+        Python::with_gil(|py| {
+            let values = |source: &str| py.eval_bound(source, None, None).unwrap();
+            let ints = Series::Int(ConcreteInt {
+                items: vec![1, 2, 3, 0].into(),
+            })
+            .take_or_null(&[Some(0), Some(1), Some(2), Some(3), None]);
+            assert_eq!(
+                ints.isin(values("[3, 'a', 1, 2.0, 2.5, True]"))
+                    .unwrap()
+                    .__repr__(),
+                "BoolSeries([true, true, true, false, false])"
+            );
+            let floats = Series::Float(ConcreteFloat {
+                items: vec![-0.0, f64::NAN, 1.5, 2.0].into(),
+            });
+            assert_eq!(
+                floats
+                    .isin(values("{0.0, float('nan'), 2, '1.5'}"))
+                    .unwrap()
+                    .__repr__(),
+                "BoolSeries([true, true, false, true])"
+            );
+            let strings = Series::String(ConcreteString {
+                items: ["a", "b", "1"].map(str::to_owned).to_vec().into(),
+            });
+            let large = Series::Int(ConcreteInt {
+                items: vec![1 << 53].into(),
+            });
+            assert_eq!(
+                large.isin(values("[2**53 + 1]")).unwrap().__repr__(),
+                "BoolSeries([false])"
+            );
+            assert_eq!(
+                strings.isin(values("('b', 1)")).unwrap().__repr__(),
+                "BoolSeries([false, true, false])"
+            );
+            let bools = Series::Bool(ConcreteBool {
+                items: vec![true, false].into(),
+            });
+            assert_eq!(
+                bools.isin(values("[1, False]")).unwrap().__repr__(),
+                "BoolSeries([false, true])"
+            );
+            let err = strings.isin(values("'ab'")).unwrap_err();
+            assert!(err.is_instance_of::<PyTypeError>(py));
+            assert!(strings.isin(values("5")).is_err());
+        });
+    }
+
+    #[test]
+    fn test_map() {
+        let series = Series::String(ConcreteString {
+            items: ["lo", "hi", "mid", "lo"].map(str::to_owned).to_vec().into(),
+        });
+        pyo3::prepare_freethreaded_python();
+        Python::with_gil(|py| {
+            let map = |source: &str| {
+                let mapping = py.eval_bound(source, None, None).unwrap();
+                series.map(py, mapping.downcast_into().unwrap())
+            };
+            assert_eq!(
+                map("{'lo': 0, 'hi': 2, 'mid': 1}").unwrap().__repr__(),
+                "IntSeries([0, 2, 1, 0])"
+            );
+            assert_eq!(
+                map("{'lo': 0, 'hi': 2.5}").unwrap().__repr__(),
+                "FloatSeries([0.0, 2.5, None, 0.0])"
+            );
+            assert_eq!(
+                map("{}").unwrap().__repr__(),
+                "FloatSeries([None, None, None, None])"
+            );
+            let err = map("{'lo': 'low', 'hi': 2}").unwrap_err();
+            assert!(err.is_instance_of::<PyValueError>(py));
+        });
+    }
+
+    #[test]
+    fn test_apply_rows() {
+        pyo3::prepare_freethreaded_python();
+        Python::with_gil(|py| {
+            let df = DataFrame {
+                item: IndexMap::from([
+                    (
+                        "a".to_owned(),
+                        Series::Int(ConcreteInt {
+                            items: vec![1, 2, 3].into(),
+                        }),
+                    ),
+                    (
+                        "b".to_owned(),
+                        Series::Float(ConcreteFloat {
+                            items: vec![0.5, 1.5, 2.5].into(),
+                        }),
+                    ),
+                ]),
+            };
+            let func = py
+                .eval_bound("lambda row: row['a'] + row['b']", None, None)
+                .unwrap();
+            let derived = df.apply_rows("total".to_owned(), func).unwrap();
+            assert_eq!(
+                derived.item["total"].__repr__(),
+                "FloatSeries([1.5, 3.5, 5.5])"
+            );
+            assert!(!df.item.contains_key("total"));
+        });
+    }
+
+    #[test]
+    fn test_csv_infinite_floats() {
+        let csv = "a,b\n1,inf\n2,-inf\n3,2.5\n4,NaN\n";
+        let df =
+            DataFrame::from_csv_reader(BufReader::new(csv.as_bytes()), &ReadOptions::default())
+                .unwrap();
+        assert_eq!(df.item["a"].__repr__(), "IntSeries([1, 2, 3, 4])");
+        assert_eq!(
+            df.item["b"].__repr__(),
+            "FloatSeries([inf, -inf, 2.5, NaN])"
+        );
+    }
+
+    #[test]
+    fn test_csv_bools() {
+        let csv = "flag,n\ntrue,1\nfalse,2\n";
+        let df =
+            DataFrame::from_csv_reader(BufReader::new(csv.as_bytes()), &ReadOptions::default())
+                .unwrap();
+        let flags = df.item["flag"].clone().bool().unwrap();
+        assert_eq!(flags.items.to_vec(), [true, false]);
+        assert!(df.item["n"].clone().bool().is_err());
+
+        // Only lowercase spellings are Bools; anything else is bare text.
+        let csv = "flag\nTrue\n";
+        let df =
+            DataFrame::from_csv_reader(BufReader::new(csv.as_bytes()), &ReadOptions::default())
+                .unwrap();
+        assert_eq!(df.item["flag"].__repr__(), "StringSeries([\"True\"])");
+    }
+
+    #[test]
+    fn test_csv_usecols() {
+        let csv = "a,b,c,d\n1,x,2.5,y\n2,z,3.5,w\n";
+        let usecols = ["a".to_owned(), "c".to_owned()];
+        let df = DataFrame::from_csv_reader(
+            BufReader::new(csv.as_bytes()),
+            &ReadOptions {
+                usecols: Some(&usecols),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        let mut columns: Vec<_> = df.item.keys().cloned().collect();
+        columns.sort();
+        assert_eq!(columns, ["a", "c"]);
+        assert_eq!(df.item["a"].__repr__(), "IntSeries([1, 2])");
+        assert_eq!(df.item["c"].__repr__(), "FloatSeries([2.5, 3.5])");
+
+        let usecols = ["a".to_owned(), "e".to_owned()];
+        assert!(DataFrame::from_csv_reader(
+            BufReader::new(csv.as_bytes()),
+            &ReadOptions {
+                usecols: Some(&usecols),
+                ..Default::default()
+            }
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_csv_sniff() {
+        let csv = "name\tscore\n\"ab\"\t1.5\n\"cd\"\t2\n";
+        let df = DataFrame::from_csv_reader(
+            BufReader::new(csv.as_bytes()),
+            &ReadOptions {
+                sniff: true,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        assert_eq!(df.columns(), ["name", "score"]);
+        assert_eq!(df.item["name"].__repr__(), "StringSeries([\"ab\", \"cd\"])");
+        assert_eq!(df.item["score"].__repr__(), "FloatSeries([1.5, 2.0])");
+
+        let csv = "1;\"x\"\n2;\"y\"\n";
+        let df = DataFrame::from_csv_reader(
+            BufReader::new(csv.as_bytes()),
+            &ReadOptions {
+                sniff: true,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        assert_eq!(df.columns(), ["col0", "col1"]);
+        assert_eq!(df.item["col0"].__repr__(), "IntSeries([1, 2])");
+    }
+
+    #[test]
+    fn test_csv_delimiter() {
+        let read = |csv: &str, delimiter| {
+            let options = ReadOptions {
+                delimiter: Some(delimiter),
+                ..Default::default()
+            };
+            DataFrame::from_csv_reader(BufReader::new(csv.as_bytes()), &options).unwrap()
+        };
+        let df = read("id\tscore\tname\n1\t2.5\t\"a,b\"\n2\t3\t\"c\"\n", '\t');
+        assert_eq!(df.columns(), ["id", "score", "name"]);
+        assert_eq!(df.item["score"].__repr__(), "FloatSeries([2.5, 3.0])");
+        assert_eq!(df.item["name"].__repr__(), "StringSeries([\"a,b\", \"c\"])");
+        let df = read("a;b\n1;2\n", ';');
+        assert_eq!(df.item["b"].__repr__(), "IntSeries([2])");
+    }
+
+    #[test]
+    fn test_csv_without_header() {
+        let read = |csv: &str| {
+            let options = ReadOptions {
+                has_header: Some(false),
+                ..Default::default()
+            };
+            DataFrame::from_csv_reader(BufReader::new(csv.as_bytes()), &options)
+        };
+        let df = read("1,2.5,\"a\"\n2,3.5,\"b\"\n").unwrap();
+        assert_eq!(df.columns(), ["col0", "col1", "col2"]);
+        assert_eq!(df.item["col0"].__repr__(), "IntSeries([1, 2])");
+        assert_eq!(df.item["col2"].__repr__(), "StringSeries([\"a\", \"b\"])");
+        assert!(read("1,2\n3\n").is_err());
+    }
+
+    #[test]
+    fn test_csv_quoted_delimiters() {
+        let csv = "id,address,note\n\
+                   1,\"12 High St, Leeds\",\"say \"\"hi\"\"\"\n\
+                   2,\"3 Low Rd\",\"\"\n";
+        let df =
+            DataFrame::from_csv_reader(BufReader::new(csv.as_bytes()), &ReadOptions::default())
+                .unwrap();
+        assert_eq!(df.item["id"].__repr__(), "IntSeries([1, 2])");
+        assert_eq!(
+            df.item["address"].__repr__(),
+            "StringSeries([\"12 High St, Leeds\", \"3 Low Rd\"])"
+        );
+        // The `csv` reader doesn't report which fields were quoted.
+        #[cfg(not(feature = "csv"))]
+        assert_eq!(
+            df.item["note"].__repr__(),
+            "StringSeries([\"say \\\"hi\\\"\", \"\"])"
+        );
+    }
+
+    #[cfg(feature = "csv")]
+    #[test]
+    fn test_csv_quoted_newlines() {
+        let csv = "id,address,note\n\
+                   1,\"12 High St\nLeeds\",plain\n\
+                   2,\"3 Low Rd, York\",\"say \"\"hi\"\"\"\n";
+        for sniff in [false, true] {
+            let df = DataFrame::from_csv_reader(
+                BufReader::new(csv.as_bytes()),
+                &ReadOptions {
+                    sniff,
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+            assert_eq!(df.item["id"].__repr__(), "IntSeries([1, 2])");
+            assert_eq!(
+                df.item["address"].__repr__(),
+                "StringSeries([\"12 High St\\nLeeds\", \"3 Low Rd, York\"])"
+            );
+            assert_eq!(
+                df.item["note"].__repr__(),
+                "StringSeries([\"plain\", \"say \\\"hi\\\"\"])"
+            );
+        }
+    }
+
+    #[test]
+    fn test_csv_nulls() {
+        let csv = "id,score,name,flag\n1,,\"a\",true\n2,2.5,,false\n3,,\"c\",true\n";
+        let df =
+            DataFrame::from_csv_reader(BufReader::new(csv.as_bytes()), &ReadOptions::default())
+                .unwrap();
+        let score = &df.item["score"];
+        assert_eq!(score.__repr__(), "FloatSeries([None, 2.5, None])");
+        assert_eq!(score.non_null_count(), 1);
+        assert_eq!(
+            df.item["name"].__repr__(),
+            "StringSeries([\"a\", None, \"c\"])"
+        );
+        assert_eq!(
+            df.take(&[2, 1]).item["score"].__repr__(),
+            "FloatSeries([None, 2.5])"
+        );
+
+        pyo3::prepare_freethreaded_python();
+        Python::with_gil(|py| {
+            let sum = score.__add__(1i64.into_py(py).into_bound(py)).unwrap();
+            assert_eq!(sum.__repr__(), "FloatSeries([None, 3.5, None])");
+            let ids = df.item["id"].clone().into_py(py).into_bound(py);
+            let sum = score.__mul__(ids).unwrap();
+            assert_eq!(sum.__repr__(), "FloatSeries([None, 5.0, None])");
+            assert!(score.get(py, 0).is_none(py));
+            assert!(score.to_var().is_err());
+        });
+
+        let mut out = vec![];
+        df.to_csv_writer(&mut out, Quoting::All).unwrap();
+        let round_trip =
+            DataFrame::from_csv_reader(BufReader::new(out.as_slice()), &ReadOptions::default())
+                .unwrap();
+        for (read, written) in round_trip.item.values().zip(df.item.values()) {
+            assert_eq!(read.__repr__(), written.__repr__());
+        }
+    }
+
+    #[test]
+    fn test_from_json() {
+        let text = r#"[
+            {"id": 1, "score": 2.5, "name": "a \"b\""},
+            {"id": 2, "name": null, "flag": true},
+            {"id": 3, "score": 4, "flag": false}
+        ]"#;
+        let df = DataFrame::from_json_str(text).unwrap();
+        assert_eq!(df.columns(), ["id", "score", "name", "flag"]);
+        assert_eq!(df.item["id"].__repr__(), "IntSeries([1, 2, 3])");
+        assert_eq!(df.item["score"].__repr__(), "FloatSeries([2.5, None, 4.0])");
+        assert_eq!(
+            df.item["name"].__repr__(),
+            "StringSeries([\"a \\\"b\\\"\", None, None])"
+        );
+        assert_eq!(
+            df.item["flag"].__repr__(),
+            "BoolSeries([None, true, false])"
+        );
+
+        for (text, message) in [
+            ("{}", "expected an array of records"),
+            ("[1]", "record 0 is not an object"),
+            (r#"[{"a": 1}, {"a": "x"}]"#, "In column \"a\""),
+            (
+                r#"[{"a": [1]}]"#,
+                "nested arrays and objects are unsupported",
+            ),
+            ("[{\"a\": 1,}]", "expected '\"' at byte 9"),
+        ] {
+            let err = DataFrame::from_json_str(text).err().unwrap().to_string();
+            assert!(err.contains(message), "{text:?}: {err}");
+        }
+    }
+
+    #[test]
+    fn test_csv_empty_edge_fields() {
+        let csv = "a,b,c\n,5,\n1,,2\n";
+        let df =
+            DataFrame::from_csv_reader(BufReader::new(csv.as_bytes()), &ReadOptions::default())
+                .unwrap();
+        assert_eq!(df.item["a"].__repr__(), "IntSeries([None, 1])");
+        assert_eq!(df.item["b"].__repr__(), "IntSeries([5, None])");
+        assert_eq!(df.item["c"].__repr__(), "IntSeries([None, 2])");
+
+        let short = "a,b,c\n1,2\n";
+        assert!(DataFrame::from_csv_reader(
+            BufReader::new(short.as_bytes()),
+            &ReadOptions::default()
+        )
+        .is_err());
+
+        // A delimiter at each end of every line, as in demo.csv, adds unnamed columns.
+        let padded = ",x,y,\n,1,\"u\",\n,2,\"v\",\n";
+        let df =
+            DataFrame::from_csv_reader(BufReader::new(padded.as_bytes()), &ReadOptions::default())
+                .unwrap();
+        assert_eq!(df.columns(), ["x", "y"]);
+        assert_eq!(df.item["x"].__repr__(), "IntSeries([1, 2])");
+    }
+
+    #[test]
+    fn test_csv_schema() {
+        let csv = "id,score,name\n1,2,\"ab\"\n";
+        let df =
+            DataFrame::from_csv_reader(BufReader::new(csv.as_bytes()), &ReadOptions::default())
+                .unwrap();
+        let schema: HashMap<String, Dtype> = df
+            .schema()
+            .into_iter()
+            .map(|(k, v)| (k, Dtype::parse(&v).unwrap()))
+            .collect();
+        assert_eq!(schema["score"], Dtype::Int);
+
+        // `score` was read as Int, so a fractional cell no longer conforms.
+        let csv = "id,score,name\n2,3.5,\"cd\"\n";
+        assert!(DataFrame::from_csv_reader(
+            BufReader::new(csv.as_bytes()),
+            &ReadOptions {
+                schema: Some(&schema),
+                ..Default::default()
+            }
+        )
+        .is_err());
+        let csv = "id,score,name\n2,3,\"cd\"\n3,4,\"ef\"\n";
+        let df = DataFrame::from_csv_reader(
+            BufReader::new(csv.as_bytes()),
+            &ReadOptions {
+                schema: Some(&schema),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        assert_eq!(df.item["score"].__repr__(), "IntSeries([3, 4])");
+        assert_eq!(df.item["name"].__repr__(), "StringSeries([\"cd\", \"ef\"])");
+
+        // Bare text is a String too, but a bare number isn't.
+        let csv = "id,score,name\n2,3,cd\n";
+        let df = DataFrame::from_csv_reader(
+            BufReader::new(csv.as_bytes()),
+            &ReadOptions {
+                schema: Some(&schema),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        assert_eq!(df.item["name"].__repr__(), "StringSeries([\"cd\"])");
+        let csv = "id,score,name\n2,3,4\n";
+        let Err(err) = DataFrame::from_csv_reader(
+            BufReader::new(csv.as_bytes()),
+            &ReadOptions {
+                schema: Some(&schema),
+                ..Default::default()
+            },
+        ) else {
+            panic!("unquoted number should not conform to String");
+        };
+        assert_eq!(
+            err.to_string(),
+            "In column \"name\": \"4\" is not a valid String"
+        );
+    }
+
+    #[test]
+    fn test_explode() {
+        let df = DataFrame {
+            item: IndexMap::from([
+                (
+                    "id".to_owned(),
+                    Series::Int(ConcreteInt {
+                        items: vec![1, 2].into(),
+                    }),
+                ),
+                (
+                    "tags".to_owned(),
+                    Series::String(ConcreteString {
+                        items: vec!["a;b;c".to_owned(), "d".to_owned()].into(),
+                    }),
+                ),
+            ]),
+        };
+        let exploded = df.explode("tags".to_owned(), ";").unwrap();
+        assert_eq!(exploded.__len__(), 4);
+        assert_eq!(exploded.item["id"].__repr__(), "IntSeries([1, 1, 1, 2])");
+        assert_eq!(
+            exploded.item["tags"].__repr__(),
+            "StringSeries([\"a\", \"b\", \"c\", \"d\"])"
+        );
+        assert!(df.explode("id".to_owned(), ";").is_err());
+        let Err(err) = df.explode("tags".to_owned(), "") else {
+            panic!("an empty separator should be rejected");
+        };
+        pyo3::prepare_freethreaded_python();
+        Python::with_gil(|py| assert!(err.is_instance_of::<PyValueError>(py)));
+
+        let nullable = DataFrame {
+            item: IndexMap::from([(
+                "tags".to_owned(),
+                Series::String(ConcreteString {
+                    items: vec!["a;b".to_owned()].into(),
+                })
+                .take_or_null(&[None, Some(0)]),
+            )]),
+        };
+        assert_eq!(
+            nullable.explode("tags".to_owned(), ";").unwrap().item["tags"].__repr__(),
+            "StringSeries([None, \"a\", \"b\"])"
+        );
+    }
+
+    #[test]
+    fn test_to_csv_quoting() {
+        let df = DataFrame {
+            item: IndexMap::from([
+                (
+                    "n".to_owned(),
+                    Series::Float(ConcreteFloat {
+                        items: vec![1.0].into(),
+                    }),
+                ),
+                (
+                    "name".to_owned(),
+                    Series::String(ConcreteString {
+                        items: vec!["Smith, John".to_owned()].into(),
+                    }),
+                ),
+                (
+                    "city".to_owned(),
+                    Series::String(ConcreteString {
+                        items: vec!["Leeds".to_owned()].into(),
+                    }),
+                ),
+            ]),
+        };
+        let write = |quoting| {
+            let mut out = vec![];
+            df.to_csv_writer(&mut out, quoting)
+                .map(|_| String::from_utf8(out).unwrap())
+        };
+        assert_eq!(
+            write(Quoting::Minimal).unwrap(),
+            "n,name,city\n1.0,\"Smith, John\",Leeds\n"
+        );
+        assert_eq!(
+            write(Quoting::All).unwrap(),
+            "\"n\",\"name\",\"city\"\n1.0,\"Smith, John\",\"Leeds\"\n"
+        );
+        assert!(write(Quoting::None).is_err());
+    }
+
+    #[test]
+    fn test_to_csv_string_round_trip() {
+        let frame = |cells: &[&str]| DataFrame {
+            item: IndexMap::from([(
+                "city".to_owned(),
+                Series::String(ConcreteString {
+                    items: cells
+                        .iter()
+                        .map(|s| s.to_string())
+                        .collect::<Vec<_>>()
+                        .into(),
+                }),
+            )]),
+        };
+        let round_trip = |df: &DataFrame, quoting| {
+            let mut out = vec![];
+            df.to_csv_writer(&mut out, quoting)?;
+            DataFrame::from_csv_reader(BufReader::new(out.as_slice()), &ReadOptions::default())
+        };
+        let plain = frame(&["Leeds", "New York"]);
+        for quoting in [Quoting::Minimal, Quoting::All, Quoting::None] {
+            let df = round_trip(&plain, quoting).unwrap();
+            assert_eq!(df.columns(), ["city"], "{quoting:?}");
+            assert_eq!(
+                df.item["city"].__repr__(),
+                plain.item["city"].__repr__(),
+                "{quoting:?}"
+            );
+        }
+        let mut cells = vec!["Leeds", "Smith, John", "say \"hi\""];
+        // The `csv` crate drops quotes, so it reads these back as an Int, a Bool and a null.
+        if cfg!(not(feature = "csv")) {
+            cells.extend(["12", "true", ""]);
+        }
+        let ambiguous = frame(&cells);
+        for quoting in [Quoting::Minimal, Quoting::All] {
+            let df = round_trip(&ambiguous, quoting).unwrap();
+            assert_eq!(
+                df.item["city"].__repr__(),
+                ambiguous.item["city"].__repr__(),
+                "{quoting:?}"
+            );
+        }
+        for cell in ["Smith, John", "12", "true", ""] {
+            assert!(
+                round_trip(&frame(&[cell]), Quoting::None).is_err(),
+                "{cell:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_infer_dtype_errors() {
+        let cells = |cells: &[&str]| cells.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+        assert_eq!(
+            Series::infer_dtype(&cells(&["1", "", "2.5", "3"])).unwrap(),
+            Dtype::Float
+        );
+        for (cells, message) in [
+            (
+                cells(&["1", "2", "\"abc", "x"]),
+                "row 2: could not infer dtype from \"\\\"abc\"",
+            ),
+            (
+                cells(&["1", "2", "abc", "x"]),
+                "row 2: Incompatible mixture of dtypes inferred: String and Int",
+            ),
+            (
+                cells(&["1", "", "true"]),
+                "row 2: Incompatible mixture of dtypes inferred: Bool and Int",
+            ),
+            (
+                cells(&[]),
+                "Empty sequence of entries provided for inference",
+            ),
+        ] {
+            assert_eq!(
+                Series::infer_dtype(&cells).unwrap_err().to_string(),
+                message
+            );
+        }
+        let csv = "a,b\n1,2\n3,oops\n";
+        let err =
+            DataFrame::from_csv_reader(BufReader::new(csv.as_bytes()), &ReadOptions::default())
+                .err()
+                .unwrap();
+        assert_eq!(
+            err.to_string(),
+            "In column \"b\": row 1: Incompatible mixture of dtypes inferred: String and Int"
+        );
+    }
+
+    #[test]
+    fn test_infer_scientific_notation() {
+        for field in ["1e10", "-3.2e-7", "4E+2", "2.3E-4", "1e5", "-0.5e0"] {
+            assert_eq!(Dtype::infer(field), Some(Dtype::Float), "{field:?}");
+        }
+        for field in ["e", "E", "1e", "e5", "1e+", "1e5e5", "1.2.3e4", "+e"] {
+            assert_eq!(Dtype::infer(field), None, "{field:?}");
+        }
+        assert_eq!(Dtype::infer("15"), Some(Dtype::Int));
+    }
+
+    #[test]
+    fn test_to_csv_float_round_trip() {
+        let floats = vec![0.1, 1.0 / 3.0, -2.5e-300, f64::MAX, f64::MIN_POSITIVE, 1e21];
+        let df = DataFrame {
+            item: IndexMap::from([
+                (
+                    "x".to_owned(),
+                    Series::Float(ConcreteFloat {
+                        items: floats.clone().into(),
+                    }),
+                ),
+                (
+                    "i".to_owned(),
+                    Series::Int(ConcreteInt {
+                        items: vec![i64::MIN, 0, 1, 2, 3, i64::MAX].into(),
+                    }),
+                ),
+            ]),
+        };
+        let mut out = vec![];
+        df.to_csv_writer(&mut out, Quoting::Minimal).unwrap();
+        let read =
+            DataFrame::from_csv_reader(BufReader::new(out.as_slice()), &ReadOptions::default())
+                .unwrap();
+        let Series::Float(ConcreteFloat { items }) = &read.item["x"] else {
+            panic!("expected a Float column, got {:?}", read.item["x"]);
+        };
+        assert_eq!(items.to_vec(), floats);
+        assert_eq!(read.item["i"].__repr__(), df.item["i"].__repr__());
+    }
+
+    #[test]
+    fn test_transform_chain() {
+        let series = Series::Float(ConcreteFloat {
+            items: vec![-5.0, 2.5, 150.0, f64::NAN].into(),
+        });
+        let chained = series
+            .clip_op(Some(Operand::Int(0)), Some(Operand::Int(100)))
+            .and_then(|s| s.pow_op(Operand::Int(2)))
+            .and_then(|s| s.mod_op(Operand::Int(7)))
+            .unwrap();
+        assert_eq!(chained.__repr__(), "FloatSeries([0.0, 6.25, 4.0, NaN])");
+        assert_eq!(series.__repr__(), "FloatSeries([-5.0, 2.5, 150.0, NaN])");
+
+        let ints = Series::Int(ConcreteInt {
+            items: vec![-1, 5, 9].into(),
+        });
+        let clipped = ints.clip_op(None, Some(Operand::Int(6))).unwrap();
+        assert_eq!(clipped.__repr__(), "IntSeries([-1, 5, 6])");
+    }
+
+    #[test]
+    fn test_insertion_point() {
+        let series = Series::Int(ConcreteInt {
+            items: vec![1, 3, 5, 7].into(),
+        });
+        assert_eq!(series.insertion_point(&Operand::Int(4), false).unwrap(), 2);
+        assert_eq!(series.insertion_point(&Operand::Int(3), false).unwrap(), 1);
+        assert_eq!(series.insertion_point(&Operand::Int(3), true).unwrap(), 2);
+        assert_eq!(
+            series.insertion_point(&Operand::Float(7.5), false).unwrap(),
+            4
+        );
+        assert_eq!(series.insertion_point(&Operand::Int(0), false).unwrap(), 0);
+
+        pyo3::prepare_freethreaded_python();
+        Python::with_gil(|py| {
+            let values = Series::Int(ConcreteInt {
+                items: vec![0, 3, 8].into(),
+            })
+            .into_py(py);
+            let positions = series
+                .searchsorted(values.into_bound(py), "left")
+                .unwrap()
+                .extract::<Series>(py)
+                .unwrap();
+            assert_eq!(positions.__repr__(), "IntSeries([0, 1, 4])");
+        });
+    }
+
+    #[test]
+    fn test_scaling() {
+        let series = Series::Int(ConcreteInt {
+            items: vec![0, 5, 10].into(),
+        });
+        assert_eq!(
+            series.minmax_scaled().unwrap().__repr__(),
+            "FloatSeries([0.0, 0.5, 1.0])"
+        );
+        let series = Series::Int(ConcreteInt {
+            items: vec![2, 4, 4, 4, 5, 5, 7, 9].into(),
+        });
+        assert_eq!(
+            series.zscored().unwrap().__repr__(),
+            "FloatSeries([-1.5, -0.5, -0.5, -0.5, 0.0, 0.0, 1.0, 2.0])"
+        );
+        let constant = Series::Float(ConcreteFloat {
+            items: vec![3.0, 3.0].into(),
+        });
+        assert_eq!(
+            constant.zscored().unwrap().__repr__(),
+            "FloatSeries([0.0, 0.0])"
+        );
+        assert_eq!(
+            constant.minmax_scaled().unwrap().__repr__(),
+            "FloatSeries([0.0, 0.0])"
+        );
+    }
+
+    #[test]
+    fn test_groupby_agg() {
+        let df = DataFrame {
+            item: IndexMap::from([
+                (
+                    "key".to_owned(),
+                    Series::String(ConcreteString {
+                        items: ["b", "a", "b", "a", "b"].map(str::to_owned).to_vec().into(),
+                    }),
+                ),
+                (
+                    "x".to_owned(),
+                    Series::Int(ConcreteInt {
+                        items: vec![1, 2, 3, 4, 5].into(),
+                    }),
+                ),
+                (
+                    "y".to_owned(),
+                    Series::Float(ConcreteFloat {
+                        items: vec![1.0, 2.0, 3.0, 4.0, 8.0].into(),
+                    }),
+                ),
+            ]),
+        };
+        pyo3::prepare_freethreaded_python();
+        Python::with_gil(|py| {
+            let aggregations = PyDict::new_bound(py);
+            aggregations.set_item("x", "sum").unwrap();
+            aggregations.set_item("y", "mean").unwrap();
+            let grouped = df
+                .groupby("key".to_owned())
+                .unwrap()
+                .agg(aggregations)
+                .unwrap();
+            assert_eq!(
+                grouped.item["key"].__repr__(),
+                "StringSeries([\"a\", \"b\"])"
+            );
+            assert_eq!(grouped.item["x"].__repr__(), "IntSeries([6, 9])");
+            assert_eq!(grouped.item["y"].__repr__(), "FloatSeries([3.0, 4.0])");
+        });
+    }
+
+    #[test]
+    fn test_groupby_nulls() {
+        let csv = "k,v,w\n1,5,1.5\n,7,2.5\n0,1,\n2,,\n1,3,0.5\n";
+        let df =
+            DataFrame::from_csv_reader(BufReader::new(csv.as_bytes()), &ReadOptions::default())
+                .unwrap();
+        pyo3::prepare_freethreaded_python();
+        let sums = df.groupby_sum("k".to_owned(), "v".to_owned()).unwrap();
+        assert_eq!(sums.item["k"].__repr__(), "IntSeries([0, 1, 2])");
+        assert_eq!(sums.item["v"].__repr__(), "IntSeries([1, 8, 0])");
+        let sums = df.groupby_sum("k".to_owned(), "w".to_owned()).unwrap();
+        assert_eq!(sums.item["w"].__repr__(), "FloatSeries([0.0, 2.0, 0.0])");
+        Python::with_gil(|py| {
+            for (column, how, expected) in [
+                ("v", "count", "IntSeries([1, 2, 0])"),
+                ("v", "max", "IntSeries([1, 5, None])"),
+                ("w", "mean", "FloatSeries([None, 1.0, None])"),
+                ("w", "min", "FloatSeries([None, 0.5, None])"),
+            ] {
+                let aggregations = PyDict::new_bound(py);
+                aggregations.set_item(column, how).unwrap();
+                let grouped = df
+                    .groupby("k".to_owned())
+                    .unwrap()
+                    .agg(aggregations)
+                    .unwrap();
+                assert_eq!(grouped.item[column].__repr__(), expected, "{how}");
+            }
+        });
+    }
+
+    #[test]
+    fn test_merge_inner() {
+        let strings = |items: &[&str]| {
+            Series::String(ConcreteString {
+                items: items.iter().map(|s| s.to_string()).collect(),
+            })
+        };
+        let left = DataFrame {
+            item: IndexMap::from([
+                ("key".to_owned(), strings(&["a", "b", "c"])),
+                (
+                    "v".to_owned(),
+                    Series::Int(ConcreteInt {
+                        items: vec![1, 2, 3].into(),
+                    }),
+                ),
+            ]),
+        };
+        let right = DataFrame {
+            item: IndexMap::from([
+                ("key".to_owned(), strings(&["c", "a", "c"])),
+                ("v".to_owned(), strings(&["x", "y", "z"])),
+            ]),
+        };
+        let suffixes = || ("_x".to_owned(), "_y".to_owned());
+        let merged = left
+            .merge(&right, "key".to_owned(), "inner", suffixes(), false)
+            .unwrap();
+        assert_eq!(
+            merged.item.keys().collect::<Vec<_>>(),
+            ["key", "v_x", "v_y"]
+        );
+        assert_eq!(
+            merged.item["key"].__repr__(),
+            "StringSeries([\"a\", \"c\", \"c\"])"
+        );
+        assert_eq!(merged.item["v_x"].__repr__(), "IntSeries([1, 3, 3])");
+        assert_eq!(
+            merged.item["v_y"].__repr__(),
+            "StringSeries([\"y\", \"x\", \"z\"])"
+        );
+
+        let floats = DataFrame {
+            item: IndexMap::from([(
+                "key".to_owned(),
+                Series::Float(ConcreteFloat {
+                    items: vec![1.0].into(),
+                }),
+            )]),
+        };
+        assert!(floats
+            .merge(&floats, "key".to_owned(), "inner", suffixes(), false)
+            .is_err());
+    }
+
+    #[test]
+    fn test_merge_null_keys() {
+        // The null key's placeholder is 0, which must not match the real 0 on either side.
+        let keys = Series::Int(ConcreteInt {
+            items: vec![0].into(),
+        })
+        .take_or_null(&[Some(0), None]);
+        let frame = DataFrame {
+            item: IndexMap::from([("key".to_owned(), keys)]),
+        };
+        let merge = |how| {
+            frame
+                .merge(
+                    &frame,
+                    "key".to_owned(),
+                    how,
+                    ("_x".to_owned(), "_y".to_owned()),
+                    true,
+                )
+                .unwrap()
+        };
+        let inner = merge("inner");
+        assert_eq!(inner.item["key"].__repr__(), "IntSeries([0])");
+        let outer = merge("outer");
+        assert_eq!(outer.item["key"].__repr__(), "IntSeries([0, None, None])");
+        assert_eq!(
+            outer.item["_merge"].__repr__(),
+            "StringSeries([\"both\", \"left_only\", \"right_only\"])"
+        );
+    }
+
+    #[test]
+    fn test_merge_suffixes_and_indicator() {
+        let left = DataFrame {
+            item: IndexMap::from([
+                (
+                    "id".to_owned(),
+                    Series::Int(ConcreteInt {
+                        items: vec![1, 2, 3].into(),
+                    }),
+                ),
+                (
+                    "v".to_owned(),
+                    Series::Int(ConcreteInt {
+                        items: vec![10, 20, 30].into(),
+                    }),
+                ),
+            ]),
+        };
+        let right = DataFrame {
+            item: IndexMap::from([
+                (
+                    "id".to_owned(),
+                    Series::Int(ConcreteInt {
+                        items: vec![2, 3, 4].into(),
+                    }),
+                ),
+                (
+                    "v".to_owned(),
+                    Series::String(ConcreteString {
+                        items: ["b", "c", "d"].map(str::to_owned).to_vec().into(),
+                    }),
+                ),
+            ]),
+        };
+        let suffixes = || ("_l".to_owned(), "_r".to_owned());
+
+        let inner = left
+            .merge(&right, "id".to_owned(), "inner", suffixes(), false)
+            .unwrap();
+        let mut columns: Vec<_> = inner.item.keys().cloned().collect();
+        columns.sort();
+        assert_eq!(columns, ["id", "v_l", "v_r"]);
+        assert_eq!(inner.item["id"].__repr__(), "IntSeries([2, 3])");
+        assert_eq!(inner.item["v_l"].__repr__(), "IntSeries([20, 30])");
+
+        let outer = left
+            .merge(&right, "id".to_owned(), "outer", suffixes(), true)
+            .unwrap();
+        assert_eq!(outer.item["id"].__repr__(), "IntSeries([1, 2, 3, 4])");
+        assert_eq!(
+            outer.item["v_l"].__repr__(),
+            "FloatSeries([10.0, 20.0, 30.0, NaN])"
+        );
+        assert_eq!(
+            outer.item["v_r"].__repr__(),
+            "StringSeries([\"\", \"b\", \"c\", \"d\"])"
+        );
+        assert_eq!(
+            outer.item["_merge"].__repr__(),
+            "StringSeries([\"left_only\", \"both\", \"both\", \"right_only\"])"
+        );
+    }
+
+    #[test]
+    fn test_rolling_sum() {
+        let a = Series::Int(ConcreteInt {
+            items: vec![1, 2, 3, 4].into(),
+        });
+        let b = Series::Float(ConcreteFloat {
+            items: vec![0.5, 1.5, 2.5, 3.5].into(),
+        });
+        let c = Series::String(ConcreteString {
+            items: vec![
+                "w".to_owned(),
+                "x".to_owned(),
+                "y".to_owned(),
+                "z".to_owned(),
+            ]
+            .into(),
+        });
+        let df = DataFrame {
+            item: IndexMap::from([
+                ("a".to_owned(), a.clone()),
+                ("b".to_owned(), b.clone()),
+                ("c".to_owned(), c),
+            ]),
+        };
+        let rolled = df.rolling(2).unwrap().sum();
+        assert_eq!(rolled.item.len(), 2);
+        assert_eq!(
+            rolled.item["a"].__repr__(),
+            a.rolling_sum(2).unwrap().__repr__()
+        );
+        assert_eq!(
+            rolled.item["b"].__repr__(),
+            b.rolling_sum(2).unwrap().__repr__()
+        );
+        assert_eq!(
+            rolled.item["a"].__repr__(),
+            "FloatSeries([NaN, 3.0, 5.0, 7.0])"
+        );
+    }
+
+    #[test]
+    fn test_nulls_in_reductions_and_element_wise_ops() {
+        let a = Series::Int(ConcreteInt {
+            items: vec![5, 7].into(),
+        })
+        .take_or_null(&[Some(0), None, Some(1)]);
+        let b = Series::Float(ConcreteFloat {
+            items: vec![1.5, 2.5].into(),
+        })
+        .take_or_null(&[Some(0), None, Some(1)]);
+        pyo3::prepare_freethreaded_python();
+        Python::with_gil(|py| {
+            let reduce = |series: &Series, how| -> f64 {
+                series.reduce(py, how).unwrap().extract(py).unwrap()
+            };
+            assert_eq!(reduce(&a, Aggregation::Sum), 12.0);
+            assert_eq!(reduce(&a, Aggregation::Min), 5.0);
+            assert_eq!(reduce(&a, Aggregation::Max), 7.0);
+            assert_eq!(reduce(&b, Aggregation::Sum), 4.0);
+            assert_eq!(reduce(&b, Aggregation::Min), 1.5);
+        });
+        assert_eq!(a.mean().unwrap(), 6.0);
+        assert_eq!(b.mean().unwrap(), 2.0);
+        assert_eq!(a.var(1).unwrap(), 2.0);
+        assert_eq!(a.argmin().unwrap(), 0);
+        assert_eq!(a.argmax().unwrap(), 2);
+        assert_eq!(a.modes().__repr__(), "IntSeries([5, 7])");
+
+        assert_eq!(a.cumsum().unwrap().__repr__(), "IntSeries([5, None, 12])");
+        assert_eq!(
+            b.cumsum().unwrap().__repr__(),
+            "FloatSeries([1.5, None, 4.0])"
+        );
+        assert_eq!(
+            a.rolling_sum(1).unwrap().__repr__(),
+            "FloatSeries([5.0, None, 7.0])"
+        );
+        assert_eq!(
+            a.rolling_sum(2).unwrap().__repr__(),
+            "FloatSeries([NaN, None, None])"
+        );
+        assert_eq!(
+            a.clip_op(Some(Operand::Int(6)), None).unwrap().__repr__(),
+            "IntSeries([6, None, 7])"
+        );
+        assert_eq!(
+            a.compare_op(Operand::Int(0), CompareOp::Eq)
+                .unwrap()
+                .__repr__(),
+            "BoolSeries([false, None, false])"
+        );
+        assert_eq!(
+            a.compare_op(Operand::Int(5), CompareOp::Ne)
+                .unwrap()
+                .__repr__(),
+            "BoolSeries([false, None, true])"
+        );
+        assert_eq!(
+            a.between_mask(Operand::Int(0), Operand::Int(1), true, true)
+                .unwrap()
+                .__repr__(),
+            "BoolSeries([false, None, false])"
+        );
+        assert_eq!(
+            a.mod_op(Operand::Series(a.clone())).unwrap().__repr__(),
+            "IntSeries([0, None, 0])"
+        );
+        assert_eq!(
+            a.minmax_scaled().unwrap().__repr__(),
+            "FloatSeries([0.0, None, 1.0])"
+        );
+        assert_eq!(
+            a.zscored().unwrap().__repr__(),
+            "FloatSeries([-1.0, None, 1.0])"
+        );
+    }
 }
@@ -0,0 +1,66 @@
+/// Booleans packed 64 to a word, for masks where a byte per element adds up.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BitVec {
+    words: Vec<u64>,
+    len: usize,
+}
+
+impl BitVec {
+    /// Bit `i` of the result is bit `i % 64` of `words[i / 64]`; bits past `len` must be zero.
+    pub(crate) fn from_words(words: Vec<u64>, len: usize) -> Self {
+        debug_assert_eq!(words.len(), len.div_ceil(64));
+        Self { words, len }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn get(&self, i: usize) -> Option<bool> {
+        (i < self.len).then(|| self.words[i / 64] >> (i % 64) & 1 == 1)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = bool> + '_ {
+        (0..self.len).map(|i| self.words[i / 64] >> (i % 64) & 1 == 1)
+    }
+
+    pub fn to_vec(&self) -> Vec<bool> {
+        self.iter().collect()
+    }
+}
+
+impl FromIterator<bool> for BitVec {
+    fn from_iter<I: IntoIterator<Item = bool>>(iter: I) -> Self {
+        let (mut words, mut len) = (vec![], 0);
+        for bit in iter {
+            if len % 64 == 0 {
+                words.push(0);
+            }
+            if bit {
+                words[len / 64] |= 1 << (len % 64);
+            }
+            len += 1;
+        }
+        Self { words, len }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip_across_word_boundaries() {
+        for len in [0, 1, 63, 64, 65, 200] {
+            let bools: Vec<bool> = (0..len).map(|i| i % 3 == 0).collect();
+            let packed: BitVec = bools.iter().copied().collect();
+            assert_eq!(packed.len(), len);
+            assert_eq!(packed.to_vec(), bools);
+            assert_eq!(packed.get(len), None);
+        }
+    }
+}
@@ -1,4 +1,6 @@
+use crate::bitvec::BitVec;
 use crate::lexer::Term;
+use crate::lexer::Token;
 use crate::lexer::Token::*;
 use crate::parser::ParseNode;
 use crate::print_tid;
@@ -6,15 +8,21 @@ use rayon::iter::IndexedParallelIterator;
 use rayon::iter::IntoParallelRefIterator;
 use rayon::iter::ParallelIterator;
 use rayon::prelude::*;
+use std::borrow::Cow;
+use std::collections::HashMap;
 use std::fmt::Debug;
 use std::result::Result;
 use std::sync::mpsc::{channel, Receiver, Sender};
-use std::sync::Arc;
+use std::sync::{Arc, OnceLock, RwLock};
+/// A column of values. Buffers are reference counted so a `Var` can share its elements with
+/// whoever produced them (e.g. a dfrs `Series`) rather than copying them.
 #[derive(Debug)]
 pub enum Var {
-    IntV(Vec<i64>),
-    FloatV(Vec<f64>),
-    BoolV(Vec<bool>),
+    IntV(Arc<Vec<i64>>),
+    FloatV(Arc<Vec<f64>>),
+    BoolV(Arc<Vec<bool>>),
+    /// Bool produced by comparison and logical operators, stored a bit per element.
+    PackedBoolV(Arc<BitVec>),
 }
 
 impl ToString for Var {
@@ -23,11 +31,12 @@ impl ToString for Var {
             Self::IntV(i) => format!("{i:?}"),
             Self::FloatV(f) => format!("{f:?}"),
             Self::BoolV(b) => format!("{b:?}"),
+            Self::PackedBoolV(b) => format!("{:?}", b.to_vec()),
         }
     }
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub enum Dtype {
     Int,
     Float,
@@ -39,7 +48,7 @@ impl Var {
         match self {
             Self::IntV(_) => Dtype::Int,
             Self::FloatV(_) => Dtype::Float,
-            Self::BoolV(_) => Dtype::Bool,
+            Self::BoolV(_) | Self::PackedBoolV(_) => Dtype::Bool,
         }
     }
 
@@ -49,9 +58,11 @@ impl Var {
             _ => Err(format!("Failed to parse i64: {self:?}")),
         }
     }
-    fn bool(&self) -> Result<&Vec<bool>, String> {
+    /// Bool elements, unpacked into a new buffer if stored packed.
+    pub fn bool(&self) -> Result<Cow<'_, [bool]>, String> {
         match self {
-            Self::BoolV(b) => Ok(b),
+            Self::BoolV(b) => Ok(Cow::Borrowed(b)),
+            Self::PackedBoolV(b) => Ok(Cow::Owned(b.to_vec())),
             _ => Err(format!("Failed to parse bool: {self:?}")),
         }
     }
@@ -64,30 +75,194 @@ impl Var {
     }
 }
 
+impl From<Vec<i64>> for Var {
+    fn from(value: Vec<i64>) -> Self {
+        Self::IntV(Arc::new(value))
+    }
+}
+
+impl From<Vec<f64>> for Var {
+    fn from(value: Vec<f64>) -> Self {
+        Self::FloatV(Arc::new(value))
+    }
+}
+
+impl From<Vec<bool>> for Var {
+    fn from(value: Vec<bool>) -> Self {
+        Self::BoolV(Arc::new(value))
+    }
+}
+
+impl From<BitVec> for Var {
+    fn from(value: BitVec) -> Self {
+        Self::PackedBoolV(Arc::new(value))
+    }
+}
+
 impl From<i64> for Var {
     fn from(value: i64) -> Self {
-        Self::IntV(vec![value])
+        Self::from(vec![value])
     }
 }
 
 impl From<f64> for Var {
     fn from(value: f64) -> Self {
-        Self::FloatV(vec![value])
+        Self::from(vec![value])
     }
 }
 
 impl From<bool> for Var {
     fn from(value: bool) -> Self {
-        Self::BoolV(vec![value])
+        Self::from(vec![value])
+    }
+}
+
+impl Var {
+    /// Splits into length-1 Vars, one per element.
+    fn elements(&self) -> Vec<Var> {
+        match self {
+            Self::IntV(i) => i.iter().map(|v| Var::from(*v)).collect(),
+            Self::FloatV(f) => f.iter().map(|v| Var::from(*v)).collect(),
+            Self::BoolV(b) => b.iter().map(|v| Var::from(*v)).collect(),
+            Self::PackedBoolV(b) => b.iter().map(Var::from).collect(),
+        }
     }
 }
 
+/// A reduction folding every element of its input into a single value, looked up by keyword
+/// (e.g. `sum :v`) through [`register_reducer`].
+pub trait Reducer: Send + Sync {
+    /// Initial accumulator for an input of the given dtype.
+    fn identity(&self, dtype: &Dtype) -> Result<Var, String>;
+    /// Folds a single-element `element` into the accumulator.
+    fn combine(&self, acc: Var, element: Var) -> Result<Var, String>;
+    /// Produces the result from the final accumulator.
+    fn finish(&self, acc: Var) -> Result<Var, String> {
+        Ok(acc)
+    }
+}
+
+/// Numeric reduction with a fixed identity and binary operation, used for the built-ins.
+/// `int_op` gives None on overflow.
+struct Fold {
+    int_identity: i64,
+    float_identity: f64,
+    int_op: fn(i64, i64) -> Option<i64>,
+    float_op: fn(f64, f64) -> f64,
+}
+
+impl Reducer for Fold {
+    fn identity(&self, dtype: &Dtype) -> Result<Var, String> {
+        match dtype {
+            Dtype::Int => Ok(Var::from(self.int_identity)),
+            Dtype::Float => Ok(Var::from(self.float_identity)),
+            Dtype::Bool => Err("Cannot reduce Bool".to_string()),
+        }
+    }
+
+    fn combine(&self, acc: Var, element: Var) -> Result<Var, String> {
+        match (acc, element) {
+            (Var::IntV(a), Var::IntV(e)) => (self.int_op)(a[0], e[0])
+                .map(Var::from)
+                .ok_or_else(|| "Int overflow".to_string()),
+            (Var::FloatV(a), Var::FloatV(e)) => Ok(Var::from((self.float_op)(a[0], e[0]))),
+            _ => Err("Invalid types".to_string()),
+        }
+    }
+}
+
+type ReducerRegistry = RwLock<HashMap<String, Arc<dyn Reducer>>>;
+
+fn reducers() -> &'static ReducerRegistry {
+    static REDUCERS: OnceLock<ReducerRegistry> = OnceLock::new();
+    REDUCERS.get_or_init(|| {
+        let builtins: [(&str, Fold); 4] = [
+            (
+                "sum",
+                Fold {
+                    int_identity: 0,
+                    float_identity: 0.0,
+                    int_op: i64::checked_add,
+                    float_op: |a, b| a + b,
+                },
+            ),
+            (
+                "prod",
+                Fold {
+                    int_identity: 1,
+                    float_identity: 1.0,
+                    int_op: i64::checked_mul,
+                    float_op: |a, b| a * b,
+                },
+            ),
+            (
+                "min",
+                Fold {
+                    int_identity: i64::MAX,
+                    float_identity: f64::INFINITY,
+                    int_op: |a, b| Some(a.min(b)),
+                    float_op: f64::min,
+                },
+            ),
+            (
+                "max",
+                Fold {
+                    int_identity: i64::MIN,
+                    float_identity: f64::NEG_INFINITY,
+                    int_op: |a, b| Some(a.max(b)),
+                    float_op: f64::max,
+                },
+            ),
+        ];
+        RwLock::new(
+            builtins
+                .into_iter()
+                .map(|(name, fold)| (name.to_owned(), Arc::new(fold) as Arc<dyn Reducer>))
+                .collect(),
+        )
+    })
+}
+
+/// Makes `reducer` available in expressions as the keyword `name`, replacing any existing
+/// reducer of that name. Names must be lowercase ASCII and not collide with other keywords.
+pub fn register_reducer(name: &str, reducer: impl Reducer + 'static) {
+    reducers()
+        .write()
+        .unwrap()
+        .insert(name.to_owned(), Arc::new(reducer));
+}
+
+pub(crate) fn reducer(name: &str) -> Option<Arc<dyn Reducer>> {
+    reducers().read().unwrap().get(name).cloned()
+}
+
+/// Values bound to `:name` variables when building an execution graph.
+pub type Bindings = HashMap<String, Arc<Var>>;
+
+/// Settings applied while building an execution graph.
+#[derive(Clone, Debug, Default)]
+pub struct GraphOptions {
+    /// `==` and `!=` treat Floats at most this far apart as equal. The default of zero
+    /// compares exactly.
+    pub float_tolerance: f64,
+}
+
 type ExecutionResult = Result<Arc<Var>, String>;
+type BinaryFn = fn(Arc<Var>, Arc<Var>) -> ExecutionResult;
+type BoxedBinaryFn = Box<dyn Fn(Arc<Var>, Arc<Var>) -> ExecutionResult + Send + Sync>;
 type SenderChannels = Vec<Sender<Arc<Var>>>;
 type ReceiverChannel = Receiver<Arc<Var>>;
 
 trait OperatorTrait: Debug {
-    fn new(parser: &ParseNode) -> Result<ExecutionGraph, ()>
+    /// Adds the operator `token` to `graph`, applied to the subgraphs at its end, where input
+    /// `i` holds `inputs[i]` ops. Returns the number of ops in the operator's own subgraph.
+    fn new(
+        token: &Token,
+        inputs: &[usize],
+        graph: &mut ExecutionGraph,
+        bindings: &Bindings,
+        options: &GraphOptions,
+    ) -> Result<usize, String>
     where
         Self: Sized;
     fn compute(&self) -> Result<(), String>;
@@ -97,6 +272,7 @@ trait OperatorTrait: Debug {
 enum OperatorEnum {
     Constant(Constant),
     BinOp(BinaryOperator),
+    Reduction(Reduction),
 }
 
 impl OperatorEnum {
@@ -104,6 +280,7 @@ impl OperatorEnum {
         match self {
             Self::Constant(c) => c.subscribe(),
             Self::BinOp(bop) => bop.subscribe(),
+            Self::Reduction(r) => r.subscribe(),
         }
     }
 
@@ -111,34 +288,75 @@ impl OperatorEnum {
         match self {
             Self::Constant(c) => c.compute(),
             Self::BinOp(bop) => bop.compute(),
+            Self::Reduction(r) => r.compute(),
+        }
+    }
+
+    /// How `explain` names the operator, and its number of inputs.
+    fn describe(&self) -> (String, usize) {
+        match self {
+            Self::Constant(c) => (
+                match &c.origin {
+                    Origin::Literal => format!("Constant {}", c.item.to_string()),
+                    Origin::Folded => format!("Constant {} (folded)", c.item.to_string()),
+                    Origin::Binding(name) => format!("Variable :{name}"),
+                },
+                0,
+            ),
+            Self::BinOp(bop) => (format!("BinOp {}", bop.symbol), 2),
+            Self::Reduction(r) => (format!("Reduce {}", r.name), 1),
         }
     }
 }
 
+/// Operators in post-order: each subgraph is contiguous, its inputs' subgraphs in order
+/// followed by its output.
 pub struct ExecutionGraph {
     ops: Vec<OperatorEnum>,
 }
 
 impl ExecutionGraph {
-    pub fn build_execution_graph(parser: &ParseNode) -> Result<Self, ()> {
-        match parser.token {
-            Plus | Neg | Mul if parser.dependencies.len() == 2 => BinaryOperator::new(parser),
-            Term(_) => Constant::new(parser),
-            _ => Err(()),
-        }
+    pub fn build_execution_graph(parser: &ParseNode) -> Result<Self, String> {
+        Self::build_execution_graph_with_bindings(parser, &Bindings::new())
     }
 
-    fn merge(&mut self, mut other: Self) -> &Self {
-        self.ops.append(&mut other.ops);
-        self
+    pub fn build_execution_graph_with_bindings(
+        parser: &ParseNode,
+        bindings: &Bindings,
+    ) -> Result<Self, String> {
+        Self::build_execution_graph_with_options(parser, bindings, &GraphOptions::default())
     }
 
-    fn current_mut(&mut self) -> Option<&mut OperatorEnum> {
-        self.ops.first_mut()
+    /// Builds the graph bottom-up with [`ParseNode::fold`], each operator pushed once onto
+    /// the end of the graph, so deeply nested expressions neither overflow the stack nor take
+    /// quadratic time.
+    pub fn build_execution_graph_with_options(
+        parser: &ParseNode,
+        bindings: &Bindings,
+        options: &GraphOptions,
+    ) -> Result<Self, String> {
+        let mut graph = Self { ops: vec![] };
+        parser.fold(|token, inputs: Vec<Result<usize, String>>| {
+            let inputs = inputs.into_iter().collect::<Result<Vec<_>, _>>()?;
+            let (inputs, graph) = (inputs.as_slice(), &mut graph);
+            match token {
+                Plus | Neg | Mul | Lt | Le | Gt | Ge | Eq | Ne | And | Or if inputs.len() == 2 => {
+                    BinaryOperator::new(token, inputs, graph, bindings, options)
+                }
+                Term(_) if inputs.is_empty() => {
+                    Constant::new(token, inputs, graph, bindings, options)
+                }
+                Reduce(_) if inputs.len() == 1 => {
+                    Reduction::new(token, inputs, graph, bindings, options)
+                }
+                _ => Err(format!("{token:?} cannot take {} operands", inputs.len())),
+            }
+        })?;
+        Ok(graph)
     }
 
     pub fn initialize(&mut self) -> Result<(), String> {
-        for c in self.ops.iter_mut().rev() {
+        for c in self.ops.iter_mut() {
             c.compute()?;
         }
         Ok(())
@@ -148,7 +366,6 @@ impl ExecutionGraph {
         let (_, fails): (Vec<_>, Vec<_>) = self
             .ops
             .par_iter_mut()
-            .rev()
             .map(|v| v.compute())
             .partition_map(|v| match v {
                 Err(_) => itertools::Either::Right(()),
@@ -162,21 +379,217 @@ impl ExecutionGraph {
     }
 
     pub fn subscribe(&mut self) -> Option<ReceiverChannel> {
-        self.current_mut().map(|v| v.subscribe())
+        self.ops.last_mut().map(|v| v.subscribe())
     }
+
+    /// The value of the subgraph of `size` ops with its output at `position`, if it is a
+    /// single literal or folded constant.
+    fn folded(&self, position: usize, size: usize) -> Option<Arc<Var>> {
+        match &self.ops[position] {
+            OperatorEnum::Constant(c) if size == 1 && !matches!(c.origin, Origin::Binding(_)) => {
+                Some(c.item.clone())
+            }
+            _ => None,
+        }
+    }
+
+    /// Replaces the `size` ops at the end of the graph with the constant `item`.
+    fn fold_tail(&mut self, size: usize, item: Arc<Var>) -> usize {
+        self.ops.truncate(self.ops.len() - size);
+        self.ops.push(Constant::op(item, Origin::Folded));
+        1
+    }
+
+    /// Describes each operator on its own line as `#index operator inputs=[...] dtype=...`,
+    /// numbered from the output at `#0` with each input's subgraph after its consumer.
+    /// Constants computed while building the graph are marked `(folded)`. The dtype is `?`
+    /// where it depends on a reducer's output.
+    pub fn explain(&self) -> String {
+        // Post-order puts every operator's inputs just before it on the stack of outputs.
+        let mut inputs: Vec<Vec<usize>> = Vec::with_capacity(self.ops.len());
+        let mut dtypes: Vec<Option<Dtype>> = Vec::with_capacity(self.ops.len());
+        let mut outputs = vec![];
+        for (position, op) in self.ops.iter().enumerate() {
+            let operands = outputs.split_off(outputs.len() - op.describe().1);
+            let operand_dtypes: Vec<_> = operands.iter().map(|i| dtypes[*i]).collect();
+            dtypes.push(match (op, operand_dtypes.as_slice()) {
+                (OperatorEnum::Constant(c), _) => Some(c.item.dtype()),
+                (OperatorEnum::BinOp(bop), _) if bop.yields_bool() => Some(Dtype::Bool),
+                (OperatorEnum::BinOp(_), [Some(Dtype::Int), Some(Dtype::Int)]) => Some(Dtype::Int),
+                (OperatorEnum::BinOp(_), [Some(_), Some(_)]) => Some(Dtype::Float),
+                _ => None,
+            });
+            inputs.push(operands);
+            outputs.push(position);
+        }
+        let mut order = vec![];
+        let mut stack = outputs;
+        while let Some(position) = stack.pop() {
+            order.push(position);
+            stack.extend(inputs[position].iter().rev());
+        }
+        let mut index = vec![0; self.ops.len()];
+        for (i, position) in order.iter().enumerate() {
+            index[*position] = i;
+        }
+        order
+            .iter()
+            .map(|position| {
+                let inputs: Vec<String> = inputs[*position]
+                    .iter()
+                    .map(|input| format!("#{}", index[*input]))
+                    .collect();
+                let dtype = dtypes[*position].map_or("?".to_string(), |d| format!("{d:?}"));
+                format!(
+                    "#{} {} inputs=[{}] dtype={dtype}",
+                    index[*position],
+                    self.ops[*position].describe().0,
+                    inputs.join(", ")
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+/// Applies `f` element-wise, broadcasting a length-1 operand across the other.
+fn broadcast<L: Sync, R: Sync, O: Send>(
+    lhs: &[L],
+    rhs: &[R],
+    f: impl Fn(&L, &R) -> O + Sync + Send,
+) -> Result<Vec<O>, String> {
+    match (lhs.len(), rhs.len()) {
+        (l, r) if l == r => Ok(lhs.par_iter().zip(rhs).map(|(x, y)| f(x, y)).collect()),
+        (1, _) => Ok(rhs.par_iter().map(|y| f(&lhs[0], y)).collect()),
+        (_, 1) => Ok(lhs.par_iter().map(|x| f(x, &rhs[0])).collect()),
+        (l, r) => Err(format!("Incompatible lengths: {l} and {r}")),
+    }
+}
+
+/// Like [`broadcast`] for predicates, packing the results a word at a time.
+fn broadcast_bits<L: Sync, R: Sync>(
+    lhs: &[L],
+    rhs: &[R],
+    f: impl Fn(&L, &R) -> bool + Sync + Send,
+) -> Result<BitVec, String> {
+    let len = match (lhs.len(), rhs.len()) {
+        (l, r) if l == r => l,
+        (1, r) => r,
+        (l, 1) => l,
+        (l, r) => return Err(format!("Incompatible lengths: {l} and {r}")),
+    };
+    let at = |i: usize, len: usize| if len == 1 { 0 } else { i };
+    let words = (0..len.div_ceil(64))
+        .into_par_iter()
+        .map(|w| {
+            let mut word = 0;
+            for i in w * 64..len.min(w * 64 + 64) {
+                if f(&lhs[at(i, lhs.len())], &rhs[at(i, rhs.len())]) {
+                    word |= 1 << (i % 64);
+                }
+            }
+            word
+        })
+        .collect();
+    Ok(BitVec::from_words(words, len))
+}
+
+fn comparison(
+    x: &Var,
+    y: &Var,
+    int_op: fn(&i64, &i64) -> bool,
+    float_op: impl Fn(&f64, &f64) -> bool + Copy + Send + Sync,
+) -> ExecutionResult {
+    let bits = match (x, y) {
+        (Var::IntV(i1), Var::IntV(i2)) => broadcast_bits(i1, i2, int_op)?,
+        (Var::FloatV(f1), Var::FloatV(f2)) => broadcast_bits(f1, f2, float_op)?,
+        (Var::FloatV(f1), Var::IntV(i2)) => {
+            broadcast_bits(f1, i2, |x, y| float_op(x, &(*y as f64)))?
+        }
+        (Var::IntV(i1), Var::FloatV(f2)) => {
+            broadcast_bits(i1, f2, |x, y| float_op(&(*x as f64), y))?
+        }
+        _ => return Err("Invalid types".to_string()),
+    };
+    Ok(Arc::new(Var::from(bits)))
+}
+
+fn logical(x: &Var, y: &Var, op: fn(&bool, &bool) -> bool) -> ExecutionResult {
+    let bits = broadcast_bits(&x.bool()?, &y.bool()?, op)?;
+    Ok(Arc::new(Var::from(bits)))
+}
+
+/// Applies `int_op` or `float_op` element-wise, promoting Int to Float when the operands
+/// differ. `int_op` gives None on overflow, which fails the whole operation.
+fn arithmetic(
+    x: &Var,
+    y: &Var,
+    int_op: fn(i64, i64) -> Option<i64>,
+    float_op: fn(f64, f64) -> f64,
+) -> ExecutionResult {
+    match (x, y) {
+        (Var::IntV(i1), Var::IntV(i2)) => {
+            let items = broadcast(i1, i2, |x, y| int_op(*x, *y))?;
+            let items: Option<Vec<i64>> = items.into_iter().collect();
+            Ok(Arc::new(Var::from(
+                items.ok_or_else(|| "Int overflow".to_string())?,
+            )))
+        }
+        (Var::FloatV(f1), Var::FloatV(f2)) => {
+            Ok(Arc::new(Var::from(broadcast(f1, f2, |x, y| {
+                float_op(*x, *y)
+            })?)))
+        }
+        (Var::FloatV(f1), Var::IntV(i2)) => Ok(Arc::new(Var::from(broadcast(f1, i2, |x, y| {
+            float_op(*x, *y as f64)
+        })?))),
+        (Var::IntV(i1), Var::FloatV(f2)) => Ok(Arc::new(Var::from(broadcast(i1, f2, |x, y| {
+            float_op(*x as f64, *y)
+        })?))),
+        _ => Err("Invalid types".to_string()),
+    }
+}
+
+/// Where a `Constant`'s value came from.
+#[derive(Debug)]
+enum Origin {
+    Literal,
+    Binding(String),
+    Folded,
 }
 
 #[derive(Debug)]
 struct Constant {
     broadcasts_to: SenderChannels,
     item: Arc<Var>,
+    origin: Origin,
+}
+
+impl Constant {
+    fn op(item: Arc<Var>, origin: Origin) -> OperatorEnum {
+        OperatorEnum::Constant(Self {
+            broadcasts_to: vec![],
+            item,
+            origin,
+        })
+    }
 }
 
 struct BinaryOperator {
+    symbol: &'static str,
     broadcasts_to: SenderChannels,
     lhs: ReceiverChannel,
     rhs: ReceiverChannel,
-    f: Box<dyn Fn(Arc<Var>, Arc<Var>) -> ExecutionResult + Send + Sync>,
+    f: BoxedBinaryFn,
+}
+
+impl BinaryOperator {
+    fn yields_bool(&self) -> bool {
+        matches!(
+            self.symbol,
+            "<" | "<=" | ">" | ">=" | "==" | "!=" | "&&" | "||"
+        )
+    }
 }
 
 impl std::fmt::Debug for BinaryOperator {
@@ -186,27 +599,28 @@ impl std::fmt::Debug for BinaryOperator {
 }
 
 impl OperatorTrait for Constant {
-    fn new(parser: &ParseNode) -> Result<ExecutionGraph, ()> {
-        if let Term(t) = &parser.token {
-            let ops: Result<Vec<OperatorEnum>, ()> = match t {
-                Term::BoolV(b) => Ok(vec![OperatorEnum::Constant(Self {
-                    broadcasts_to: vec![],
-                    item: Arc::new(Var::BoolV(vec![*b])),
-                })]),
-                Term::IntV(i) => Ok(vec![OperatorEnum::Constant(Self {
-                    broadcasts_to: vec![],
-                    item: Arc::new(Var::IntV(vec![*i])),
-                })]),
-                Term::FloatV(f) => Ok(vec![OperatorEnum::Constant(Self {
-                    broadcasts_to: vec![],
-                    item: Arc::new(Var::FloatV(vec![*f])),
-                })]),
-                _ => Err(()),
-            };
-            ops.map(|v| ExecutionGraph { ops: v })
+    fn new(
+        token: &Token,
+        _inputs: &[usize],
+        graph: &mut ExecutionGraph,
+        bindings: &Bindings,
+        _options: &GraphOptions,
+    ) -> Result<usize, String> {
+        let op = if let Term(t) = token {
+            match t {
+                Term::BoolV(b) => Self::op(Arc::new(Var::from(*b)), Origin::Literal),
+                Term::IntV(i) => Self::op(Arc::new(Var::from(*i)), Origin::Literal),
+                Term::FloatV(f) => Self::op(Arc::new(Var::from(*f)), Origin::Literal),
+                Term::Var(name) => bindings
+                    .get(name)
+                    .ok_or_else(|| format!("Unbound variable :{name}"))
+                    .map(|item| Self::op(item.clone(), Origin::Binding(name.clone())))?,
+            }
         } else {
-            Err(())
-        }
+            return Err(format!("{token:?} is not a constant"));
+        };
+        graph.ops.push(op);
+        Ok(1)
     }
 
     fn compute(&self) -> Result<(), String> {
@@ -227,91 +641,99 @@ impl OperatorTrait for Constant {
 }
 
 impl OperatorTrait for BinaryOperator {
-    fn new(parser: &ParseNode) -> Result<ExecutionGraph, ()> {
-        if let [lhs, rhs] = parser.dependencies.as_slice() {
-            let mut lhs_op = ExecutionGraph::build_execution_graph(lhs)?;
-            let mut rhs_op = ExecutionGraph::build_execution_graph(rhs)?;
-            let lhs = lhs_op.current_mut().unwrap().subscribe();
-            let rhs = rhs_op.current_mut().unwrap().subscribe();
-            let broadcasts_to: SenderChannels = vec![];
-            let f = match &parser.token {
-                Plus => |x: Arc<Var>, y: Arc<Var>| match (x.as_ref(), y.as_ref()) {
-                    (Var::IntV(i1), Var::IntV(i2)) => Ok(Arc::new(Var::IntV(
-                        i1.par_iter().zip(i2).map(|(x, y)| x + y).collect(),
-                    ))),
-                    (Var::FloatV(f1), Var::FloatV(f2)) => Ok(Arc::new(Var::FloatV(
-                        f1.par_iter().zip(f2).map(|(x, y)| x + y).collect(),
-                    ))),
-                    (Var::FloatV(f1), Var::IntV(i2)) => Ok(Arc::new(Var::FloatV(
-                        f1.par_iter()
-                            .zip(i2)
-                            .map(|(x, y)| x + (*y as f64))
-                            .collect(),
-                    ))),
-                    (Var::IntV(i1), Var::FloatV(f2)) => Ok(Arc::new(Var::FloatV(
-                        i1.par_iter()
-                            .zip(f2)
-                            .map(|(x, y)| (*x as f64) + y)
-                            .collect(),
-                    ))),
-                    _ => Err("Invalid types".to_string()),
-                },
-                Mul => |x: Arc<Var>, y: Arc<Var>| match (x.as_ref(), y.as_ref()) {
-                    (Var::IntV(i1), Var::IntV(i2)) => Ok(Arc::new(Var::IntV(
-                        i1.par_iter().zip(i2).map(|(x, y)| x * y).collect(),
-                    ))),
-                    (Var::FloatV(f1), Var::FloatV(f2)) => Ok(Arc::new(Var::FloatV(
-                        f1.par_iter().zip(f2).map(|(x, y)| x * y).collect(),
-                    ))),
-                    (Var::FloatV(f1), Var::IntV(i2)) => Ok(Arc::new(Var::FloatV(
-                        f1.par_iter()
-                            .zip(i2)
-                            .map(|(x, y)| x * (*y as f64))
-                            .collect(),
-                    ))),
-                    (Var::IntV(i1), Var::FloatV(f2)) => Ok(Arc::new(Var::FloatV(
-                        i1.par_iter()
-                            .zip(f2)
-                            .map(|(x, y)| (*x as f64) * y)
-                            .collect(),
-                    ))),
-                    _ => Err("Invalid types".to_string()),
-                },
-                Sub => |x: Arc<Var>, y: Arc<Var>| match (x.as_ref(), y.as_ref()) {
-                    (Var::IntV(i1), Var::IntV(i2)) => Ok(Arc::new(Var::IntV(
-                        i1.par_iter().zip(i2).map(|(x, y)| x - y).collect(),
-                    ))),
-                    (Var::FloatV(f1), Var::FloatV(f2)) => Ok(Arc::new(Var::FloatV(
-                        f1.par_iter().zip(f2).map(|(x, y)| x - y).collect(),
-                    ))),
-                    (Var::FloatV(f1), Var::IntV(i2)) => Ok(Arc::new(Var::FloatV(
-                        f1.par_iter()
-                            .zip(i2)
-                            .map(|(x, y)| x - (*y as f64))
-                            .collect(),
-                    ))),
-                    (Var::IntV(i1), Var::FloatV(f2)) => Ok(Arc::new(Var::FloatV(
-                        i1.par_iter()
-                            .zip(f2)
-                            .map(|(x, y)| (*x as f64) - y)
-                            .collect(),
-                    ))),
-                    _ => Err("Invalid types".to_string()),
-                },
-                _ => todo!("Haven't filled in all the `BinOp`"),
+    fn new(
+        token: &Token,
+        inputs: &[usize],
+        graph: &mut ExecutionGraph,
+        _bindings: &Bindings,
+        options: &GraphOptions,
+    ) -> Result<usize, String> {
+        if let [lhs_size, rhs_size] = *inputs {
+            // Each subgraph ends with its output.
+            let rhs_op = graph.ops.len() - 1;
+            let lhs_op = rhs_op - rhs_size;
+            let (symbol, f): (_, BinaryFn) = match token {
+                Plus => ("+", |x: Arc<Var>, y: Arc<Var>| {
+                    arithmetic(&x, &y, i64::checked_add, |a, b| a + b)
+                }),
+                Mul => ("*", |x: Arc<Var>, y: Arc<Var>| {
+                    arithmetic(&x, &y, i64::checked_mul, |a, b| a * b)
+                }),
+                Neg => ("-", |x: Arc<Var>, y: Arc<Var>| {
+                    arithmetic(&x, &y, i64::checked_sub, |a, b| a - b)
+                }),
+                Lt => ("<", |x: Arc<Var>, y: Arc<Var>| {
+                    comparison(&x, &y, |a, b| a < b, |a, b| a < b)
+                }),
+                Le => ("<=", |x: Arc<Var>, y: Arc<Var>| {
+                    comparison(&x, &y, |a, b| a <= b, |a, b| a <= b)
+                }),
+                Gt => (">", |x: Arc<Var>, y: Arc<Var>| {
+                    comparison(&x, &y, |a, b| a > b, |a, b| a > b)
+                }),
+                Ge => (">=", |x: Arc<Var>, y: Arc<Var>| {
+                    comparison(&x, &y, |a, b| a >= b, |a, b| a >= b)
+                }),
+                Eq => ("==", |x: Arc<Var>, y: Arc<Var>| {
+                    comparison(&x, &y, |a, b| a == b, |a, b| a == b)
+                }),
+                Ne => ("!=", |x: Arc<Var>, y: Arc<Var>| {
+                    comparison(&x, &y, |a, b| a != b, |a, b| a != b)
+                }),
+                And => ("&&", |x: Arc<Var>, y: Arc<Var>| {
+                    logical(&x, &y, |a, b| *a && *b)
+                }),
+                Or => ("||", |x: Arc<Var>, y: Arc<Var>| {
+                    logical(&x, &y, |a, b| *a || *b)
+                }),
+                _ => return Err(format!("{token:?} is not a binary operator")),
+            };
+            let eps = options.float_tolerance;
+            // Exact equality first, so infinities of the same sign are equal; NaN equals nothing.
+            let f: BoxedBinaryFn = match token {
+                Eq if eps > 0.0 => Box::new(move |x: Arc<Var>, y: Arc<Var>| {
+                    comparison(
+                        &x,
+                        &y,
+                        |a, b| a == b,
+                        move |a, b| a == b || (a - b).abs() <= eps,
+                    )
+                }),
+                Ne if eps > 0.0 => Box::new(move |x: Arc<Var>, y: Arc<Var>| {
+                    comparison(
+                        &x,
+                        &y,
+                        |a, b| a != b,
+                        move |a, b| !(a == b || (a - b).abs() <= eps),
+                    )
+                }),
+                _ => Box::new(f),
             };
+            // Fold literal operands now. The kernels are checked, so folding can fail but not
+            // panic; a failed fold keeps the operator so its error surfaces from execution.
+            let (lhs, rhs) = (
+                graph.folded(lhs_op, lhs_size),
+                graph.folded(rhs_op, rhs_size),
+            );
+            if let (Some(x), Some(y)) = (lhs, rhs) {
+                if let Ok(item) = f(x, y) {
+                    return Ok(graph.fold_tail(lhs_size + rhs_size, item));
+                }
+            }
+            let lhs = graph.ops[lhs_op].subscribe();
+            let rhs = graph.ops[rhs_op].subscribe();
+            let broadcasts_to: SenderChannels = vec![];
             let binop = OperatorEnum::BinOp(Self {
+                symbol,
                 broadcasts_to,
                 lhs,
                 rhs,
-                f: Box::new(f),
+                f,
             });
-            let mut g = ExecutionGraph { ops: vec![binop] };
-            g.merge(lhs_op);
-            g.merge(rhs_op);
-            Ok(g)
+            graph.ops.push(binop);
+            Ok(1 + lhs_size + rhs_size)
         } else {
-            Err(())
+            Err(format!("{token:?} takes two operands"))
         }
     }
 
@@ -335,6 +757,75 @@ impl OperatorTrait for BinaryOperator {
     }
 }
 
+struct Reduction {
+    name: String,
+    broadcasts_to: SenderChannels,
+    input: ReceiverChannel,
+    reducer: Arc<dyn Reducer>,
+}
+
+impl std::fmt::Debug for Reduction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Reduction").finish()
+    }
+}
+
+fn reduce(reducer: &dyn Reducer, input: &Var) -> ExecutionResult {
+    let mut acc = reducer.identity(&input.dtype())?;
+    for element in input.elements() {
+        acc = reducer.combine(acc, element)?;
+    }
+    Ok(Arc::new(reducer.finish(acc)?))
+}
+
+impl OperatorTrait for Reduction {
+    fn new(
+        token: &Token,
+        inputs: &[usize],
+        graph: &mut ExecutionGraph,
+        _bindings: &Bindings,
+        _options: &GraphOptions,
+    ) -> Result<usize, String> {
+        if let (Reduce(name), &[input_size]) = (token, inputs) {
+            let reducer = reducer(name).ok_or_else(|| format!("Unknown reducer `{name}`"))?;
+            let input_op = graph.ops.len() - 1;
+            // As for binary operators, a failed fold is left to fail again at execution.
+            if let Some(item) = graph.folded(input_op, input_size) {
+                if let Ok(item) = reduce(reducer.as_ref(), &item) {
+                    return Ok(graph.fold_tail(input_size, item));
+                }
+            }
+            let input = graph.ops[input_op].subscribe();
+            let reduction = OperatorEnum::Reduction(Self {
+                name: name.clone(),
+                broadcasts_to: vec![],
+                input,
+                reducer,
+            });
+            graph.ops.push(reduction);
+            Ok(1 + input_size)
+        } else {
+            Err(format!("{token:?} is not a reduction over one operand"))
+        }
+    }
+
+    fn compute(&self) -> Result<(), String> {
+        print_tid!();
+        let input = self.input.recv().map_err(|e| e.to_string())?;
+        let result = reduce(self.reducer.as_ref(), &input)?;
+        for subscriber in &self.broadcasts_to {
+            subscriber.send(result.clone()).map_err(|e| e.to_string())?;
+        }
+        Ok(())
+    }
+
+    fn subscribe(&mut self) -> Receiver<Arc<Var>> {
+        let (sender, receiver): (Sender<Arc<Var>>, Receiver<Arc<Var>>) = channel();
+        self.broadcasts_to.push(sender);
+        receiver
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::{lexer::lex, parser::parse};
@@ -377,6 +868,342 @@ mod tests {
         assert_eq!(result.i64().unwrap().to_owned(), vec![65]);
     }
 
+    #[test]
+    fn scalar_broadcasts_across_binding() {
+        let tokens = lex(":v + 10".chars()).unwrap();
+        let ast = parse(&tokens).unwrap();
+        let bindings = Bindings::from([("v".to_owned(), Arc::new(Var::from(vec![1, 2, 3])))]);
+        let mut g = ExecutionGraph::build_execution_graph_with_bindings(&ast, &bindings).unwrap();
+        let handle = g.subscribe().unwrap();
+        g.initialize().unwrap();
+        let result = handle.recv().unwrap();
+        assert_eq!(result.i64().unwrap().to_owned(), vec![11, 12, 13]);
+
+        let tokens = lex("2.5 * :v".chars()).unwrap();
+        let ast = parse(&tokens).unwrap();
+        let mut g = ExecutionGraph::build_execution_graph_with_bindings(&ast, &bindings).unwrap();
+        let handle = g.subscribe().unwrap();
+        g.initialize().unwrap();
+        let result = handle.recv().unwrap();
+        assert_eq!(result.f64().unwrap().to_owned(), vec![2.5, 5.0, 7.5]);
+
+        let tokens = lex(":u".chars()).unwrap();
+        let ast = parse(&tokens).unwrap();
+        assert!(ExecutionGraph::build_execution_graph_with_bindings(&ast, &bindings).is_err());
+    }
+
+    #[test]
+    fn comparison_results_are_packed() {
+        let values: Vec<i64> = (0..10_000).map(|i| (i * 7919) % 1000).collect();
+        let bindings = Bindings::from([("v".to_owned(), Arc::new(Var::from(values.clone())))]);
+        let tokens = lex(":v < 500".chars()).unwrap();
+        let ast = parse(&tokens).unwrap();
+        let mut g = ExecutionGraph::build_execution_graph_with_bindings(&ast, &bindings).unwrap();
+        let handle = g.subscribe().unwrap();
+        g.initialize().unwrap();
+        let result = handle.recv().unwrap();
+        assert!(matches!(result.as_ref(), Var::PackedBoolV(_)));
+        let expected: Vec<bool> = values.iter().map(|v| *v < 500).collect();
+        assert_eq!(result.bool().unwrap().as_ref(), expected.as_slice());
+
+        let bindings = Bindings::from([
+            ("a".to_owned(), result.clone()),
+            ("b".to_owned(), Arc::new(Var::from(vec![true; 10_000]))),
+        ]);
+        let tokens = lex(":a && :b".chars()).unwrap();
+        let ast = parse(&tokens).unwrap();
+        let mut g = ExecutionGraph::build_execution_graph_with_bindings(&ast, &bindings).unwrap();
+        let handle = g.subscribe().unwrap();
+        g.initialize().unwrap();
+        assert_eq!(handle.recv().unwrap().bool().unwrap().as_ref(), expected);
+    }
+
+    #[test]
+    fn float_tolerance() {
+        // `+` binds tighter than `==`, so the sum is the rhs of `==`.
+        let tokens = lex("0.3 == 0.1 + 0.2".chars()).unwrap();
+        let ast = parse(&tokens).unwrap();
+        for (tolerance, expected) in [(0.0, false), (1e-9, true)] {
+            let options = GraphOptions {
+                float_tolerance: tolerance,
+            };
+            let mut g = ExecutionGraph::build_execution_graph_with_options(
+                &ast,
+                &Bindings::new(),
+                &options,
+            )
+            .unwrap();
+            let handle = g.subscribe().unwrap();
+            g.initialize().unwrap();
+            assert_eq!(handle.recv().unwrap().bool().unwrap().as_ref(), [expected]);
+        }
+
+        let bindings = Bindings::from([
+            (
+                "x".to_owned(),
+                Arc::new(Var::from(vec![1.0, f64::INFINITY, f64::NAN])),
+            ),
+            ("inf".to_owned(), Arc::new(Var::from(vec![f64::INFINITY]))),
+            ("nan".to_owned(), Arc::new(Var::from(vec![f64::NAN]))),
+        ]);
+        let options = GraphOptions {
+            float_tolerance: 1e-9,
+        };
+        for (program, expected) in [
+            (":x == :inf", [false, true, false]),
+            (":x != :inf", [true, false, true]),
+            (":x != :nan", [true, true, true]),
+            (":x == :x", [true, true, false]),
+        ] {
+            let tokens = lex(program.chars()).unwrap();
+            let ast = parse(&tokens).unwrap();
+            let mut g =
+                ExecutionGraph::build_execution_graph_with_options(&ast, &bindings, &options)
+                    .unwrap();
+            let handle = g.subscribe().unwrap();
+            g.initialize().unwrap();
+            assert_eq!(
+                handle.recv().unwrap().bool().unwrap().as_ref(),
+                expected,
+                "{program}"
+            );
+        }
+    }
+
+    struct Range;
+
+    impl Reducer for Range {
+        fn identity(&self, dtype: &Dtype) -> Result<Var, String> {
+            match dtype {
+                Dtype::Int => Ok(Var::from(Vec::<i64>::new())),
+                _ => Err("range only supports Int".to_string()),
+            }
+        }
+
+        fn combine(&self, acc: Var, element: Var) -> Result<Var, String> {
+            let (acc, e) = (acc.i64()?.clone(), element.i64()?[0]);
+            match acc.as_slice() {
+                [] => Ok(Var::from(vec![e, e])),
+                [lo, hi] => Ok(Var::from(vec![e.min(*lo), e.max(*hi)])),
+                _ => Err("Invalid accumulator".to_string()),
+            }
+        }
+
+        fn finish(&self, acc: Var) -> Result<Var, String> {
+            let acc = acc.i64()?;
+            Ok(Var::from(acc[1] - acc[0]))
+        }
+    }
+
+    #[test]
+    fn reductions() {
+        register_reducer("range", Range);
+        let bindings = Bindings::from([("v".to_owned(), Arc::new(Var::from(vec![3, 9, 1, 4])))]);
+        for (program, expected) in [("range :v", 8), ("sum :v", 17), ("max :v + 1", 10)] {
+            let tokens = lex(program.chars()).unwrap();
+            let ast = parse(&tokens).unwrap();
+            let mut g =
+                ExecutionGraph::build_execution_graph_with_bindings(&ast, &bindings).unwrap();
+            let handle = g.subscribe().unwrap();
+            g.initialize().unwrap();
+            let result = handle.recv().unwrap();
+            assert_eq!(result.i64().unwrap().to_owned(), vec![expected]);
+        }
+    }
+
+    #[test]
+    fn explain() {
+        let tokens = lex("5 * (10 + 3)".chars()).unwrap();
+        let g = ExecutionGraph::build_execution_graph(&parse(&tokens).unwrap()).unwrap();
+        assert_eq!(g.explain(), "#0 Constant [65] (folded) inputs=[] dtype=Int");
+
+        let tokens = lex(":v * (10 + 3)".chars()).unwrap();
+        let bindings = Bindings::from([("v".to_owned(), Arc::new(Var::from(vec![0.5])))]);
+        let g = ExecutionGraph::build_execution_graph_with_bindings(
+            &parse(&tokens).unwrap(),
+            &bindings,
+        )
+        .unwrap();
+        assert_eq!(
+            g.explain(),
+            "#0 BinOp * inputs=[#1, #2] dtype=Float\n\
+             #1 Variable :v inputs=[] dtype=Float\n\
+             #2 Constant [13] (folded) inputs=[] dtype=Int"
+        );
+    }
+
+    #[test]
+    fn failed_folds_are_left_to_execution() {
+        for (program, plan) in [
+            (
+                "9223372036854775807 + 1",
+                "#0 BinOp + inputs=[#1, #2] dtype=Int\n\
+                 #1 Constant [9223372036854775807] inputs=[] dtype=Int\n\
+                 #2 Constant [1] inputs=[] dtype=Int",
+            ),
+            (
+                "max true",
+                "#0 Reduce max inputs=[#1] dtype=?\n\
+                 #1 Constant [true] inputs=[] dtype=Bool",
+            ),
+        ] {
+            let tokens = lex(program.chars()).unwrap();
+            let mut g = ExecutionGraph::build_execution_graph(&parse(&tokens).unwrap()).unwrap();
+            assert_eq!(g.explain(), plan);
+            assert!(g.initialize().is_err(), "{program:?}");
+        }
+        let tokens = lex("9223372036854775806 + 1".chars()).unwrap();
+        let g = ExecutionGraph::build_execution_graph(&parse(&tokens).unwrap()).unwrap();
+        assert_eq!(
+            g.explain(),
+            "#0 Constant [9223372036854775807] (folded) inputs=[] dtype=Int"
+        );
+    }
+
+    #[test]
+    fn malformed_programs_do_not_panic() {
+        let bindings = Bindings::from([("v".to_owned(), Arc::new(Var::from(vec![1, 2])))]);
+        for program in [
+            "",
+            " ",
+            "+",
+            "-",
+            "*",
+            "1 +",
+            "* 2",
+            "+ 1",
+            "(",
+            ")",
+            "()",
+            "((1)",
+            "(1))",
+            "1 2",
+            "1 + + 2",
+            "--1",
+            "- - -",
+            "sum",
+            "sum sum 1",
+            "sum (",
+            ":",
+            ":u",
+            ":v +",
+            "sin",
+            "sin 1",
+            "cos :v",
+            "true + 1",
+            "true * false",
+            "1 < 2",
+            "2.5 < sin 3",
+            "1 < < 2",
+            "1.2.3",
+            "1e400",
+            "99999999999999999999",
+            "nan * inf",
+            "-inf",
+            "max true",
+            "prod :v * :v",
+            ":v + :v * (1 + 2)",
+            "(1 + 2) * 3",
+            "9223372036854775807 + 1",
+            "3 * 9223372036854775807",
+            "0 - 9223372036854775807 - 2",
+            ":v + 9223372036854775807",
+            "sum (:v * 9223372036854775807)",
+            "prod (:v + 9223372036854775806)",
+            "1 + (",
+            "a",
+            "int",
+            "#",
+        ] {
+            let Ok(tokens) = lex(program.chars()) else {
+                continue;
+            };
+            let Ok(ast) = parse(&tokens) else {
+                continue;
+            };
+            let Ok(mut g) = ExecutionGraph::build_execution_graph_with_bindings(&ast, &bindings)
+            else {
+                continue;
+            };
+            let _ = g.explain();
+            let handle = g.subscribe().unwrap();
+            if g.initialize().is_ok() {
+                assert!(handle.recv().is_ok(), "{program:?}");
+            }
+        }
+
+        // Int overflow is an error rather than a wrapped result.
+        for program in ["9223372036854775807 + 1", "sum (:v * 9223372036854775807)"] {
+            let ast = parse(&lex(program.chars()).unwrap()).unwrap();
+            let mut g =
+                ExecutionGraph::build_execution_graph_with_bindings(&ast, &bindings).unwrap();
+            assert!(g.initialize().is_err(), "{program:?}");
+        }
+
+        // Trees the parser wouldn't produce should be rejected rather than panic.
+        let leaf = || ParseNode {
+            dependencies: vec![],
+            token: Term(Term::IntV(1)),
+        };
+        for tree in [
+            ParseNode {
+                dependencies: vec![leaf()],
+                token: Mul,
+            },
+            ParseNode {
+                dependencies: vec![leaf(), leaf()],
+                token: Sin,
+            },
+            ParseNode {
+                dependencies: vec![leaf(), leaf()],
+                token: Reduce("sum".to_owned()),
+            },
+            ParseNode {
+                dependencies: vec![leaf()],
+                token: Reduce("unregistered".to_owned()),
+            },
+        ] {
+            assert!(ExecutionGraph::build_execution_graph(&tree).is_err());
+        }
+
+        // Unknown words lex as reducers and are rejected once the graph resolves them.
+        let tokens = lex("avg :v".chars()).unwrap();
+        let err = ExecutionGraph::build_execution_graph_with_bindings(
+            &parse(&tokens).unwrap(),
+            &bindings,
+        )
+        .err()
+        .unwrap();
+        assert_eq!(err, "Unknown reducer `avg`");
+    }
+
+    #[test]
+    fn deep_expressions() {
+        let depth = 50_000;
+        let program = format!("{}1{}", "1 + (".repeat(depth), ")".repeat(depth));
+        let ast = parse(&lex(program.chars()).unwrap()).unwrap();
+        let mut g = ExecutionGraph::build_execution_graph(&ast).unwrap();
+        let handle = g.subscribe().unwrap();
+        g.initialize().unwrap();
+        assert_eq!(
+            handle.recv().unwrap().i64().unwrap().to_owned(),
+            vec![depth as i64 + 1]
+        );
+
+        // A bound variable at every level keeps each operator from folding.
+        let program = format!("{}:x{}", ":x + (".repeat(depth), ")".repeat(depth));
+        let ast = parse(&lex(program.chars()).unwrap()).unwrap();
+        let bindings = Bindings::from([("x".to_owned(), Arc::new(Var::from(vec![2])))]);
+        let mut g = ExecutionGraph::build_execution_graph_with_bindings(&ast, &bindings).unwrap();
+        assert_eq!(g.explain().lines().count(), 2 * depth + 1);
+        let handle = g.subscribe().unwrap();
+        g.initialize().unwrap();
+        assert_eq!(
+            handle.recv().unwrap().i64().unwrap().to_owned(),
+            vec![2 * (depth as i64 + 1)]
+        );
+    }
+
     #[test]
     fn end_to_end_with_par_iter() {
         let program = "5 * (10 + 3)";
@@ -12,7 +12,7 @@ pub struct LexError {
 
 impl std::fmt::Display for LexError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "LexError")
+        write!(f, "LexError: {}", self.substr)
     }
 }
 
@@ -38,6 +38,8 @@ pub enum Token {
     RightParen,
     Sin,
     Cos,
+    Ceil,
+    Cast,
     Mul,
     Lt,
     Le,
@@ -48,6 +50,9 @@ pub enum Token {
     And,
     Or,
     Ne,
+    /// Reduction named by this word, e.g. `sum`. The name is only looked up among the
+    /// registered reducers when building the execution graph.
+    Reduce(String),
 }
 
 struct PeekIter<'a, Item> {
@@ -75,23 +80,6 @@ impl<'a, V: Copy> PeekIter<'a, V> {
     }
 }
 
-impl<'a, V: Copy + PartialEq> PeekIter<'a, V> {
-    fn consume_if_matches<I: Iterator<Item = V>>(&mut self, item: I) -> bool {
-        // peek all the way, if it matches advance iterator
-        let mut count = 0;
-        for (i, v) in item.enumerate() {
-            if self.peek(i) != Some(v) {
-                return false;
-            }
-            count += 1;
-        }
-        for _ in 0..count {
-            self.next();
-        }
-        true
-    }
-}
-
 impl<'a, V> Iterator for PeekIter<'a, V> {
     type Item = V;
     fn next(&mut self) -> Option<Self::Item> {
@@ -127,170 +115,179 @@ pub fn lex_multiline(program: &str) -> LexResult<Vec<Vec<Token>>> {
     }
 }
 
+/// Streams tokens lazily from a character iterator, stopping after the first error.
+pub struct LexIter<'a> {
+    it: PeekIter<'a, char>,
+    failed: bool,
+}
+
+impl<'a> Iterator for LexIter<'a> {
+    type Item = LexResult<Token>;
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.failed {
+            return None;
+        }
+        let c = self.it.find(|c| *c != ' ')?;
+        let token = lex_token(&mut self.it, c);
+        self.failed = token.is_err();
+        Some(token)
+    }
+}
+
+pub fn lex_iter<'a, I: Iterator<Item = char> + 'a>(program: I) -> LexIter<'a> {
+    LexIter {
+        it: PeekIter::consume_iter(program),
+        failed: false,
+    }
+}
+
 pub fn lex<I: Iterator<Item = char>>(program: I) -> LexResult<Vec<Token>> {
     print_tid!("lex");
-    let mut it = PeekIter::consume_iter(program);
-    let mut token_stream = vec![];
-    while let Some(c) = it.next() {
-        let token = match c {
-            '(' => Token::LeftParen,
-            ')' => Token::RightParen,
-            '+' => Token::Plus,
-            '-' => Token::Neg,
-            '*' => Token::Mul,
-            '<' => {
-                if let Some('=') = it.peek(0) {
-                    it.next();
-                    Token::Le
-                } else {
-                    Token::Lt
-                }
+    lex_iter(program).collect()
+}
+
+/// Reserved words, matched against a whole run of lowercase letters. Other words lex as
+/// reducer names.
+const KEYWORDS: &[(&str, Token)] = &[
+    ("true", Token::Term(Term::BoolV(true))),
+    ("false", Token::Term(Term::BoolV(false))),
+    ("inf", Token::Term(Term::FloatV(f64::INFINITY))),
+    ("nan", Token::Term(Term::FloatV(f64::NAN))),
+    ("sin", Token::Sin),
+    ("cos", Token::Cos),
+    ("ceil", Token::Ceil),
+    ("cast", Token::Cast),
+];
+
+fn lex_token(it: &mut PeekIter<'_, char>, c: char) -> LexResult<Token> {
+    let token = match c {
+        '(' => Token::LeftParen,
+        ')' => Token::RightParen,
+        '+' => Token::Plus,
+        '-' => Token::Neg,
+        '*' => Token::Mul,
+        '<' => {
+            if let Some('=') = it.peek(0) {
+                it.next();
+                Token::Le
+            } else {
+                Token::Lt
             }
-            '>' => {
-                if let Some('=') = it.peek(0) {
-                    it.next();
-                    Token::Ge
-                } else {
-                    Token::Gt
-                }
+        }
+        '>' => {
+            if let Some('=') = it.peek(0) {
+                it.next();
+                Token::Ge
+            } else {
+                Token::Gt
             }
-            '&' => {
-                if let Some('&') = it.peek(0) {
-                    it.next();
-                    Token::And
-                } else {
-                    return Err(LexError {
-                        substr: "Failed to parse `and`".to_owned(),
-                    });
-                }
+        }
+        '&' => {
+            if let Some('&') = it.peek(0) {
+                it.next();
+                Token::And
+            } else {
+                return Err(LexError {
+                    substr: "Failed to parse `and`".to_owned(),
+                });
             }
-            '|' => {
-                if let Some('|') = it.peek(0) {
-                    it.next();
-                    Token::Or
-                } else {
-                    return Err(LexError {
-                        substr: "Failed to parse `or`".to_owned(),
-                    });
-                }
+        }
+        '|' => {
+            if let Some('|') = it.peek(0) {
+                it.next();
+                Token::Or
+            } else {
+                return Err(LexError {
+                    substr: "Failed to parse `or`".to_owned(),
+                });
             }
-            '!' => {
-                if let Some('=') = it.peek(0) {
-                    it.next();
-                    Token::Ne
-                } else {
-                    Token::Not
-                }
+        }
+        '!' => {
+            if let Some('=') = it.peek(0) {
+                it.next();
+                Token::Ne
+            } else {
+                Token::Not
             }
-            '=' => {
-                if let Some('=') = it.peek(0) {
-                    it.next();
-                    Token::Eq
-                } else {
-                    return Err(LexError {
-                        substr: "Failed to parse `eq`".to_owned(),
-                    });
-                }
+        }
+        '=' => {
+            if let Some('=') = it.peek(0) {
+                it.next();
+                Token::Eq
+            } else {
+                return Err(LexError {
+                    substr: "Failed to parse `eq`".to_owned(),
+                });
             }
-            '0'..='9' => {
-                let mut numeric_float = false;
-                let mut str_rep = c.to_string();
-                loop {
-                    let peek = it.peek(0);
-                    match peek {
-                        Some('.') => {
-                            if numeric_float {
-                                return Err(LexError {
-                                    substr: "Failed; cannot have multiple `.` in numeric literal"
-                                        .to_string(),
-                                });
-                            } else {
-                                numeric_float = true;
-                                str_rep.push(it.next().unwrap());
-                            }
-                        }
-                        Some('0'..='9') => {
+        }
+        '0'..='9' => {
+            let mut numeric_float = false;
+            let mut str_rep = c.to_string();
+            loop {
+                let peek = it.peek(0);
+                match peek {
+                    Some('.') => {
+                        if numeric_float {
+                            return Err(LexError {
+                                substr: "Failed; cannot have multiple `.` in numeric literal"
+                                    .to_string(),
+                            });
+                        } else {
+                            numeric_float = true;
                             str_rep.push(it.next().unwrap());
                         }
-                        _ => {
-                            break;
-                        }
                     }
-                }
-                if numeric_float {
-                    Token::Term(Term::FloatV(str_rep.parse().map_err(|_| LexError {
-                        substr: "parse error".to_owned(),
-                    })?))
-                } else {
-                    Token::Term(Term::IntV(str_rep.parse().map_err(|_| LexError {
-                        substr: "parse error".to_owned(),
-                    })?))
-                }
-            }
-            't' => {
-                if it.consume_if_matches("rue".chars()) {
-                    Token::Term(Term::BoolV(true))
-                } else {
-                    return Err(LexError {
-                        substr: format!("Failed to parse `true`"),
-                    });
+                    Some('0'..='9') => {
+                        str_rep.push(it.next().unwrap());
+                    }
+                    _ => {
+                        break;
+                    }
                 }
             }
-            'f' => {
-                if it.consume_if_matches("alse".chars()) {
-                    Token::Term(Term::BoolV(false))
-                } else {
-                    return Err(LexError {
-                        substr: format!("Failed to parse `false`"),
-                    });
-                }
+            if numeric_float {
+                Token::Term(Term::FloatV(str_rep.parse().map_err(|_| LexError {
+                    substr: "parse error".to_owned(),
+                })?))
+            } else {
+                Token::Term(Term::IntV(str_rep.parse().map_err(|_| LexError {
+                    substr: "parse error".to_owned(),
+                })?))
             }
-            's' => {
-                if it.consume_if_matches("in".chars()) {
-                    Token::Sin
-                } else {
-                    return Err(LexError {
-                        substr: format!("Failed to parse `sin`"),
-                    });
-                }
+        }
+        'a'..='z' => {
+            let mut word = c.to_string();
+            while let Some(c @ 'a'..='z') = it.peek(0) {
+                word.push(c);
+                it.next();
             }
-            'c' => {
-                if it.consume_if_matches("cos".chars()) {
-                    Token::Cos
-                } else {
-                    return Err(LexError {
-                        substr: format!("Failed to parse `cos`"),
-                    });
-                }
+            match KEYWORDS.iter().find(|(keyword, _)| *keyword == word) {
+                Some((_, token)) => token.clone(),
+                None => Token::Reduce(word),
             }
-            ':' => {
-                // Variables signified with ':'
-                let mut var_name = String::new();
-                while let Some(c) = it.peek(0) {
-                    match c {
-                        'a'..='z' => {
-                            var_name.push(it.next().unwrap());
-                        }
-                        _ => {
-                            break;
-                        }
+        }
+        ':' => {
+            // Variables signified with ':'
+            let mut var_name = String::new();
+            while let Some(c) = it.peek(0) {
+                match c {
+                    'a'..='z' => {
+                        var_name.push(it.next().unwrap());
+                    }
+                    _ => {
+                        break;
                     }
                 }
-                Token::Term(Term::Var(var_name))
-            }
-            ' ' => {
-                continue;
-            }
-            _ => {
-                return Err(LexError {
-                    substr: format!("Unexpected character: {c}"),
-                })
             }
-        };
-        token_stream.push(token);
-    }
-
-    Ok(token_stream)
+            Token::Term(Term::Var(var_name))
+        }
+        _ => {
+            return Err(LexError {
+                substr: format!("Unexpected character: {c}"),
+            })
+        }
+    };
+    Ok(token)
 }
 
 #[cfg(test)]
@@ -327,6 +324,62 @@ mod tests {
         let program = "false";
         let result = lex(program.chars()).unwrap();
         assert_eq!(result, vec![Token::Term(BoolV(false)),]);
+
+        let program = "-inf";
+        let result = lex(program.chars()).unwrap();
+        assert_eq!(
+            result,
+            vec![Token::Neg, Token::Term(FloatV(f64::INFINITY)),]
+        );
+
+        let program = "nan";
+        let result = lex(program.chars()).unwrap();
+        assert!(matches!(result.as_slice(), [Token::Term(FloatV(f)),] if f.is_nan()));
+
+        let program = "int";
+        let result = lex(program.chars()).unwrap();
+        assert_eq!(result, vec![Token::Reduce("int".to_owned())]);
+
+        let program = "cos sum :v";
+        let result = lex(program.chars()).unwrap();
+        assert_eq!(
+            result,
+            vec![
+                Token::Cos,
+                Token::Reduce("sum".to_owned()),
+                Token::Term(Var("v".to_owned())),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_keywords_sharing_a_prefix() {
+        for (program, token) in [
+            ("cos", Token::Cos),
+            ("cast", Token::Cast),
+            ("ceil", Token::Ceil),
+        ] {
+            assert_eq!(lex(program.chars()).unwrap(), vec![token]);
+        }
+        for word in ["co", "ceiling"] {
+            assert_eq!(
+                lex(word.chars()).unwrap(),
+                vec![Token::Reduce(word.to_owned())]
+            );
+        }
+    }
+
+    #[test]
+    fn test_lex_iter() {
+        let program = "((10.3 - 9) > :input) || !false";
+        let streamed: LexResult<Vec<Token>> = lex_iter(program.chars()).collect();
+        assert_eq!(streamed.unwrap(), lex(program.chars()).unwrap());
+
+        let mut it = lex_iter("1 + ? + 2".chars());
+        assert_eq!(it.next().unwrap().unwrap(), Token::Term(Term::IntV(1)));
+        assert_eq!(it.next().unwrap().unwrap(), Token::Plus);
+        it.next().unwrap().expect_err("Unexpected character `?`");
+        assert!(it.next().is_none());
     }
 
     #[test]
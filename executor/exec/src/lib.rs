@@ -1,4 +1,6 @@
 use rayon::prelude::*;
+use std::sync::Arc;
+mod bitvec;
 mod execution;
 /// We want to execute a DSL for arithmetic operations
 /// 1. We have the following operations: Add, Mul, Div, Sub, Lt, Le, Gt, Ge, Eq, Log, Exp, Sin, Cos, Tan, Cast, Const, And, Or, Xor.
@@ -7,10 +9,15 @@ mod execution;
 /// b. Note that arithmetic operations don't apply for bool, only logical operations.
 /// c. No implicit promotion/casting.
 /// 3. We operate on Vec<i64>, Vec<f64> and Vec<bool>
-mod lexer;
-mod parser;
+pub mod lexer;
+pub mod parser;
 mod utils;
 
+pub use bitvec::BitVec;
+pub use execution::{
+    register_reducer, Bindings, Dtype, ExecutionGraph, GraphOptions, Reducer, Var,
+};
+
 pub type EvaluatableResult = Result<String, ()>;
 pub trait Evaluatable {
     fn to_owned_string(self) -> EvaluatableResult;
@@ -30,6 +37,14 @@ impl Evaluatable for std::fs::File {
 }
 
 pub fn evaluate(input: impl Evaluatable) -> Result<Vec<String>, Box<dyn std::error::Error>> {
+    evaluate_with_bindings(input, &Bindings::new())
+}
+
+/// Evaluates each line of the program with `:name` variables resolved from `bindings`.
+pub fn evaluate_with_bindings(
+    input: impl Evaluatable,
+    bindings: &Bindings,
+) -> Result<Vec<String>, Box<dyn std::error::Error>> {
     let program = input.to_owned_string().map_err(|_| "Failed")?;
     let tokens = lexer::lex_multiline(&program)?;
     let (asts, fails): (Vec<_>, Vec<_>) = tokens
@@ -43,18 +58,55 @@ pub fn evaluate(input: impl Evaluatable) -> Result<Vec<String>, Box<dyn std::err
     if !fails.is_empty() {
         println!("Failed to parse: {fails:?}");
     }
-    let mut gs: Vec<_> = asts
+    let mut gs = asts
         .iter()
-        .map(execution::ExecutionGraph::build_execution_graph)
-        .map(|g| g.unwrap())
-        .collect();
+        .map(|ast| execution::ExecutionGraph::build_execution_graph_with_bindings(ast, bindings))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| format!("Failed to build execution graph: {e}"))?;
 
     let mut results = vec![];
     for g in &mut gs {
-        let handle = g.subscribe().unwrap();
-        g.initialize_par_iter().unwrap();
-        let result = handle.recv().unwrap();
+        let handle = g.subscribe().ok_or("Empty execution graph")?;
+        g.initialize_par_iter()?;
+        let result = handle.recv()?;
         results.push(result.as_ref().to_string());
     }
     Ok(results)
 }
+
+/// One result per line, or why the program couldn't be read.
+type LineResults<T> = Result<Vec<Result<T, String>>, Box<dyn std::error::Error>>;
+
+/// Evaluates each line of the program independently, so one failing line doesn't hide the
+/// results of the others.
+pub fn evaluate_each_with_bindings(
+    input: impl Evaluatable,
+    bindings: &Bindings,
+) -> LineResults<String> {
+    Ok(evaluate_each_to_vars(input, bindings)?
+        .into_iter()
+        .map(|result| result.map(|var| var.to_string()))
+        .collect())
+}
+
+/// Like [`evaluate_each_with_bindings`], keeping each result as the evaluated vector.
+pub fn evaluate_each_to_vars(
+    input: impl Evaluatable,
+    bindings: &Bindings,
+) -> LineResults<Arc<Var>> {
+    let program = input.to_owned_string().map_err(|_| "Failed")?;
+    Ok(program
+        .lines()
+        .map(|line| evaluate_line(line, bindings))
+        .collect())
+}
+
+fn evaluate_line(line: &str, bindings: &Bindings) -> Result<Arc<Var>, String> {
+    let tokens = lexer::lex(line.chars()).map_err(|e| e.to_string())?;
+    let ast = parser::parse(&tokens).map_err(|e| format!("Failed to parse {line}: {e}"))?;
+    let mut g = ExecutionGraph::build_execution_graph_with_bindings(&ast, bindings)
+        .map_err(|e| format!("Failed to build execution graph for {line}: {e}"))?;
+    let handle = g.subscribe().ok_or("Empty execution graph")?;
+    g.initialize_par_iter()?;
+    handle.recv().map_err(|e| e.to_string())
+}
@@ -4,7 +4,25 @@ use crate::print_tid;
 use std::result::Result;
 
 #[derive(Debug)]
-pub struct ParseError;
+pub struct ParseError {
+    message: String,
+}
+
+impl ParseError {
+    fn new(message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+        }
+    }
+}
+
+impl std::fmt::Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "ParseError: {}", self.message)
+    }
+}
+
+impl std::error::Error for ParseError {}
 
 pub type ParseResult<T> = Result<T, ParseError>;
 
@@ -13,10 +31,69 @@ pub struct ParseNode {
     pub(crate) dependencies: Vec<ParseNode>,
     pub(crate) token: Token,
 }
+
+impl ParseNode {
+    pub fn token(&self) -> &Token {
+        &self.token
+    }
+
+    pub fn dependencies(&self) -> &[ParseNode] {
+        &self.dependencies
+    }
+
+    /// Reduces the tree bottom-up: `f` receives each node's token and its folded dependencies.
+    /// Walks an explicit stack, so the depth of the tree is bounded only by memory.
+    pub fn fold<T>(&self, mut f: impl FnMut(&Token, Vec<T>) -> T) -> T {
+        // Each node is visited twice: first to queue its dependencies, then, once they are
+        // folded, to fold it.
+        let mut stack = vec![(self, false)];
+        let mut folded: Vec<T> = vec![];
+        while let Some((node, ready)) = stack.pop() {
+            if ready {
+                let dependencies = folded.split_off(folded.len() - node.dependencies.len());
+                folded.push(f(&node.token, dependencies));
+            } else {
+                stack.push((node, true));
+                stack.extend(node.dependencies.iter().rev().map(|d| (d, false)));
+            }
+        }
+        folded.pop().unwrap()
+    }
+
+    /// Names of the `:name` variables the expression references, in first-use order.
+    pub fn variables(&self) -> Vec<&str> {
+        let mut names = vec![];
+        let mut stack = vec![self];
+        while let Some(node) = stack.pop() {
+            if let Term(Term::Var(name)) = &node.token {
+                if !names.contains(&name.as_str()) {
+                    names.push(name);
+                }
+            }
+            stack.extend(node.dependencies.iter().rev());
+        }
+        names
+    }
+}
+
+impl Drop for ParseNode {
+    /// Drops the dependencies from an explicit stack, as the default recursive drop would
+    /// overflow the call stack on deep trees.
+    fn drop(&mut self) {
+        let mut stack = std::mem::take(&mut self.dependencies);
+        while let Some(mut node) = stack.pop() {
+            stack.append(&mut node.dependencies);
+        }
+    }
+}
 // TODO: easy type inference?
 // Grammar:
-// expr -> term (binop expr)? | unop expr | '(' expr ')'
+// expr -> operand (binop operand)*
+// operand -> unop operand | '(' expr ')' | term
 // term -> Int | Bool | Float | Var
+// Binary operators associate to the left. From loosest to tightest they are `||`, `&&`,
+// comparisons, `+`/`-`, then `*`; prefix operators bind tighter still, so `sin 1 + 2` adds
+// 2 to `sin 1`.
 
 fn parse_term(term: &Term) -> ParseResult<ParseNode> {
     Ok(ParseNode {
@@ -25,64 +102,181 @@ fn parse_term(term: &Term) -> ParseResult<ParseNode> {
     })
 }
 
-fn parse_expr<'s>(tokens: &'s [Token]) -> ParseResult<(ParseNode, &'s [Token])> {
-    let (node, remaining_slice) = tokens.split_first().ok_or_else(|| ParseError {})?;
-    match node {
-        LeftParen => {
-            // Parse subexpr and then validate ')' matching parenthesis.
-            let (subexpr, rest) = parse_expr(remaining_slice)?;
-            let (last, restrest) = rest.split_first().ok_or_else(|| ParseError {})?;
-            match last {
-                RightParen => Ok((subexpr, restrest)),
-                _ => Err(ParseError {}),
+/// How tightly a binary operator binds, or None if `token` isn't one.
+fn precedence(token: &Token) -> Option<u8> {
+    match token {
+        Or => Some(1),
+        And => Some(2),
+        Lt | Le | Gt | Ge | Eq | Ne => Some(3),
+        Neg | Plus => Some(4),
+        Mul => Some(5),
+        _ => None,
+    }
+}
+
+/// Position in a token slice, with lookahead in the style of the lexer's `PeekIter`.
+struct Cursor<'t> {
+    tokens: &'t [Token],
+    position: usize,
+}
+
+impl<'t> Cursor<'t> {
+    fn new(tokens: &'t [Token]) -> Self {
+        Self {
+            tokens,
+            position: 0,
+        }
+    }
+
+    /// The token `n` places ahead of the next one, without consuming anything.
+    fn peek(&self, n: usize) -> Option<&'t Token> {
+        self.tokens.get(self.position + n)
+    }
+
+    /// Consumes the next token, which must start an expression.
+    fn advance(&mut self) -> ParseResult<&'t Token> {
+        let token = self
+            .peek(0)
+            .ok_or_else(|| ParseError::new("expected an expression, found end of input"))?;
+        self.position += 1;
+        Ok(token)
+    }
+
+    /// Consumes the next token if it is `expected`.
+    fn expect(&mut self, expected: Token) -> ParseResult<()> {
+        match self.peek(0) {
+            Some(token) if *token == expected => {
+                self.position += 1;
+                Ok(())
             }
+            found => Err(ParseError::new(format!(
+                "expected {expected:?}, found {}",
+                describe(found)
+            ))),
         }
-        Term(term) => {
-            let term = parse_term(term)?;
-            // Now is there a binary operator?
-            if let Some((binop_term, rest)) = remaining_slice.split_first() {
-                // Note; no type checking even though it could be feasible here
-                match binop_term {
-                    RightParen => Ok((term, remaining_slice)),
-                    Neg | Plus | Mul | Lt | Le | Gt | Ge | Eq | And | Or | Ne => {
-                        // Parse rhs expr
-                        let (rhs, residual) = parse_expr(rest)?;
-                        Ok((
-                            ParseNode {
-                                dependencies: vec![term, rhs],
-                                token: binop_term.clone(),
-                            },
-                            residual,
-                        ))
-                    }
-                    _ => Err(ParseError),
-                }
-            } else {
-                Ok((term, remaining_slice))
+    }
+}
+
+fn describe(token: Option<&Token>) -> String {
+    token.map_or("end of input".to_string(), |t| format!("{t:?}"))
+}
+
+/// An operator waiting on operands still being parsed.
+enum Pending {
+    /// `'('` whose closing `')'` hasn't been reached.
+    Paren,
+    /// Binary operator whose lhs is on the operand stack.
+    Binary(Token),
+    /// Prefix operator applied to the operand being parsed.
+    Unary(Token),
+}
+
+/// Operators and the operands they will combine, shunting-yard style.
+#[derive(Default)]
+struct Stacks {
+    pending: Vec<Pending>,
+    operands: Vec<ParseNode>,
+    open_parens: usize,
+}
+
+impl Stacks {
+    /// Applies the prefix operators waiting on the operand just completed.
+    fn apply_unary(&mut self) {
+        while let Some(Pending::Unary(token)) =
+            self.pending.pop_if(|p| matches!(p, Pending::Unary(_)))
+        {
+            let operand = self.operands.pop().unwrap();
+            self.operands.push(ParseNode {
+                dependencies: vec![operand],
+                token,
+            });
+        }
+    }
+
+    /// Combines operands under the pending binary operators that bind at least as tightly as
+    /// `min_precedence`, stopping at an open parenthesis.
+    fn apply_binary(&mut self, min_precedence: u8) {
+        while let Some(Pending::Binary(token)) = self.pending.pop_if(
+            |p| matches!(p, Pending::Binary(token) if precedence(token) >= Some(min_precedence)),
+        ) {
+            let rhs = self.operands.pop().unwrap();
+            let lhs = self.operands.pop().unwrap();
+            self.operands.push(ParseNode {
+                dependencies: vec![lhs, rhs],
+                token,
+            });
+        }
+    }
+}
+
+/// Parses `expr` with explicit stacks rather than recursion, so nesting depth is bounded
+/// only by memory. Stops before a `')'` it didn't open.
+fn parse_expr(cursor: &mut Cursor<'_>) -> ParseResult<ParseNode> {
+    let mut stacks = Stacks::default();
+    loop {
+        // An operand, after any prefix operators and opening parentheses.
+        match cursor.advance()? {
+            LeftParen => {
+                stacks.pending.push(Pending::Paren);
+                stacks.open_parens += 1;
+                continue;
+            }
+            token @ (Neg | Plus | Sin | Cos | Ceil | Cast | Reduce(_)) => {
+                stacks.pending.push(Pending::Unary(token.clone()));
+                continue;
+            }
+            Term(term) => stacks.operands.push(parse_term(term)?),
+            token => {
+                return Err(ParseError::new(format!(
+                    "expected an expression, found {token:?}"
+                )))
             }
         }
-        Neg | Plus | Sin | Cos => {
-            let (subexpr, rest) = parse_expr(remaining_slice)?;
-            Ok((
-                ParseNode {
-                    dependencies: vec![subexpr],
-                    token: node.clone(),
+        stacks.apply_unary();
+        // Then closing parentheses, each completing another operand, and a binary operator.
+        loop {
+            match cursor.peek(0) {
+                Some(RightParen) | None if stacks.open_parens > 0 => {
+                    cursor.expect(RightParen)?;
+                    stacks.apply_binary(0);
+                    stacks.pending.pop();
+                    stacks.open_parens -= 1;
+                    stacks.apply_unary();
+                }
+                None | Some(RightParen) => {
+                    stacks.apply_binary(0);
+                    return Ok(stacks.operands.pop().unwrap());
+                }
+                // Note; no type checking even though it could be feasible here
+                Some(binop) => match precedence(binop) {
+                    Some(p) => {
+                        cursor.advance()?;
+                        stacks.apply_binary(p);
+                        stacks.pending.push(Pending::Binary(binop.clone()));
+                        break;
+                    }
+                    None => {
+                        return Err(ParseError::new(format!(
+                            "expected an operator, found {binop:?}"
+                        )))
+                    }
                 },
-                rest,
-            ))
+            }
         }
-        _ => Err(ParseError {}),
     }
 }
 
-// There are *zero* type checks or error messages (just if it succeeds or not).
+// There are *zero* type checks (just if it succeeds or not, and where it failed).
 pub fn parse(tokens: &[Token]) -> ParseResult<ParseNode> {
     print_tid!("parse");
-    let (node, remaining) = parse_expr(tokens)?;
-    if remaining.is_empty() {
-        Ok(node)
-    } else {
-        Err(ParseError {})
+    let mut cursor = Cursor::new(tokens);
+    let node = parse_expr(&mut cursor)?;
+    match cursor.peek(0) {
+        None => Ok(node),
+        found => Err(ParseError::new(format!(
+            "expected end of input, found {}",
+            describe(found)
+        ))),
     }
 }
 
@@ -106,4 +300,130 @@ mod tests {
         let tokens = lex(":a * :b < 102".chars()).unwrap();
         parse(&tokens).unwrap();
     }
+
+    /// The parse of `program` written out with every operation parenthesized.
+    fn render(program: &str) -> String {
+        let tokens = lex(program.chars()).unwrap();
+        parse(&tokens).unwrap().fold(|token, deps: Vec<String>| {
+            let op = match token {
+                Term(Term::IntV(i)) => return i.to_string(),
+                Term(Term::Var(v)) => return format!(":{v}"),
+                Reduce(name) => name,
+                Neg => "-",
+                Plus => "+",
+                Mul => "*",
+                Lt => "<",
+                Gt => ">",
+                Eq => "==",
+                Ne => "!=",
+                And => "&&",
+                Or => "||",
+                Sin => "sin",
+                Cos => "cos",
+                _ => unreachable!(),
+            };
+            match deps.as_slice() {
+                [lhs, rhs] => format!("({lhs} {op} {rhs})"),
+                [operand] => format!("({op} {operand})"),
+                _ => unreachable!(),
+            }
+        })
+    }
+
+    #[test]
+    fn test_precedence() {
+        for (program, expected) in [
+            (":a * :b < 102", "((:a * :b) < 102)"),
+            ("1 - 2 - 3", "((1 - 2) - 3)"),
+            ("1 + 2 * 3 - 4", "((1 + (2 * 3)) - 4)"),
+            (":price > 100 && :qty < 5", "((:price > 100) && (:qty < 5))"),
+            (":a || :b && :c", "(:a || (:b && :c))"),
+            ("1 == 2 != 3", "((1 == 2) != 3)"),
+            ("(1 + 2) * 3", "((1 + 2) * 3)"),
+            ("(:a < 1) || (:b > 2)", "((:a < 1) || (:b > 2))"),
+            ("((1))", "1"),
+            ("sin 1 + 2", "((sin 1) + 2)"),
+            ("- - 1 * 2", "((- (- 1)) * 2)"),
+            ("sum :v * cos (1 + 2)", "((sum :v) * (cos (1 + 2)))"),
+        ] {
+            assert_eq!(render(program), expected, "{program:?}");
+        }
+    }
+
+    #[test]
+    fn test_deep_nesting() {
+        let depth = 50_000;
+        let program = format!("{}1{}", "(".repeat(depth), ")".repeat(depth));
+        let tokens = lex(program.chars()).unwrap();
+        assert!(matches!(parse(&tokens).unwrap().token, Term(Term::IntV(1))));
+        let tokens =
+            lex(format!("{}1{}", "(".repeat(depth), ")".repeat(depth - 1)).chars()).unwrap();
+        assert!(parse(&tokens).is_err());
+
+        // Unlike parentheses, each `+` adds a node, so the tree is as deep as the nesting.
+        let program = format!("{}1{}", "1 + (".repeat(depth), ")".repeat(depth));
+        let ast = parse(&lex(program.chars()).unwrap()).unwrap();
+        assert_eq!(
+            ast.fold(|_, deps: Vec<usize>| 1 + deps.iter().sum::<usize>()),
+            2 * depth + 1
+        );
+        assert!(ast.variables().is_empty());
+    }
+
+    #[test]
+    fn test_error_messages() {
+        for (program, message) in [
+            ("(1", "expected RightParen, found end of input"),
+            ("(sin (1) 2)", "expected an operator, found Term(IntV(2))"),
+            ("(1 + 2", "expected RightParen, found end of input"),
+            ("((1) + 2", "expected RightParen, found end of input"),
+            ("1 +", "expected an expression, found end of input"),
+            ("1 sin 2", "expected an operator, found Sin"),
+            ("1)", "expected end of input, found RightParen"),
+        ] {
+            let tokens = lex(program.chars()).unwrap();
+            let err = parse(&tokens).unwrap_err();
+            assert_eq!(
+                err.to_string(),
+                format!("ParseError: {message}"),
+                "{program:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_variables() {
+        let tokens = lex(":b + :a * (sin :b < 3)".chars()).unwrap();
+        assert_eq!(parse(&tokens).unwrap().variables(), ["b", "a"]);
+        let tokens = lex("1 + 2".chars()).unwrap();
+        assert!(parse(&tokens).unwrap().variables().is_empty());
+    }
+
+    #[test]
+    fn test_fold() {
+        let tokens = lex("1 + :a * (2.5 < sin 3)".chars()).unwrap();
+        let ast = parse(&tokens).unwrap();
+        let source = ast.fold(|token, deps: Vec<String>| {
+            let op = match token {
+                Term(Term::IntV(i)) => return i.to_string(),
+                Term(Term::FloatV(f)) => return f.to_string(),
+                Term(Term::Var(v)) => return format!(":{v}"),
+                Plus => "+",
+                Mul => "*",
+                Lt => "<",
+                Sin => "sin",
+                _ => unreachable!(),
+            };
+            match deps.as_slice() {
+                [lhs, rhs] => format!("({lhs} {op} {rhs})"),
+                [operand] => format!("({op} {operand})"),
+                _ => unreachable!(),
+            }
+        });
+        assert_eq!(source, "(1 + (:a * (2.5 < (sin 3))))");
+        assert_eq!(
+            ast.fold(|_, deps: Vec<usize>| 1 + deps.iter().sum::<usize>()),
+            8
+        );
+    }
 }
@@ -1,13 +1,19 @@
+/// Logs the current operator's type, or `$name`, with the thread running it. Only prints
+/// with the `trace` feature, so library users don't get stderr noise.
 #[macro_export]
 macro_rules! print_tid {
     () => {
-        eprintln!(
-            "{:?} -> {:?}",
-            std::any::type_name::<Self>(),
-            std::thread::current().id()
-        );
+        if cfg!(feature = "trace") {
+            eprintln!(
+                "{:?} -> {:?}",
+                std::any::type_name::<Self>(),
+                std::thread::current().id()
+            );
+        }
     };
     ($name:tt) => {
-        eprintln!("{:?} -> {:?}", $name, std::thread::current().id());
+        if cfg!(feature = "trace") {
+            eprintln!("{:?} -> {:?}", $name, std::thread::current().id());
+        }
     };
 }
@@ -1,3 +1,6 @@
 fn main() -> Result<(), std::io::Error> {
-    tonic_build::compile_protos("proto/execserver.proto")
+    let out_dir = std::path::PathBuf::from(std::env::var("OUT_DIR").unwrap());
+    tonic_build::configure()
+        .file_descriptor_set_path(out_dir.join("execserver_descriptor.bin"))
+        .compile_protos(&["proto/execserver.proto"], &["proto"])
 }
@@ -1,4 +1,5 @@
 use execserver::executor_service_client::ExecutorServiceClient;
+use execserver::expression_result::Outcome;
 use execserver::ExpressionRequest;
 
 mod execserver {
@@ -11,9 +12,18 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         .nth(1)
         .ok_or_else(|| "Provide expression")?;
     let mut client = ExecutorServiceClient::connect("http://[::1]:50051").await?;
-    let request = tonic::Request::new(ExpressionRequest { expression });
+    let request = tonic::Request::new(ExpressionRequest {
+        expression,
+        ..Default::default()
+    });
     let response = client.execute_expression(request).await?;
 
-    eprintln!("Response: {:?}", response);
+    for (line, result) in response.into_inner().results.into_iter().enumerate() {
+        match result.outcome {
+            Some(Outcome::Value(value)) => eprintln!("{line}: {}", value.value),
+            Some(Outcome::Error(error)) => eprintln!("{line}: error: {}", error.message),
+            None => eprintln!("{line}: no result"),
+        }
+    }
     Ok(())
 }
@@ -1,10 +1,20 @@
-use exec::evaluate;
+use exec::{evaluate_each_to_vars, Bindings, Var};
+use execserver::column::Values;
 use execserver::executor_service_server::{ExecutorService, ExecutorServiceServer};
-use execserver::{ExpressionRequest, ExpressionResponse};
+use execserver::expression_result::Outcome;
+use execserver::{
+    BoolColumn, Column, ErrorResult, ExpressionRequest, ExpressionResponse, ExpressionResult,
+    FloatColumn, IntColumn, ValueResult,
+};
+use std::sync::Arc;
 use tonic::{transport::Server, Request, Response, Status};
 
 mod execserver {
     tonic::include_proto!("execserver");
+
+    #[cfg(feature = "reflection")]
+    pub(crate) const FILE_DESCRIPTOR_SET: &[u8] =
+        tonic::include_file_descriptor_set!("execserver_descriptor");
 }
 
 #[derive(Debug, Default)]
@@ -16,12 +26,54 @@ impl ExecutorService for ExecutorRpcServer {
         &self,
         request: Request<ExpressionRequest>,
     ) -> Result<Response<ExpressionResponse>, Status> {
-        let evaluated_result = evaluate(request.into_inner().expression)
+        let request = request.into_inner();
+        let bindings = request
+            .bindings
+            .into_iter()
+            .map(|(name, column)| Ok((name, Arc::new(to_var(column)?))))
+            .collect::<Result<Bindings, String>>()
+            .map_err(Status::invalid_argument)?;
+        let evaluated = evaluate_each_to_vars(request.expression, &bindings)
             .map_err(|e| Status::aborted(e.to_string()))?;
-        let expression_result = ExpressionResponse {
-            result: format!("Your response is: {:?}", evaluated_result),
-        };
-        Ok(Response::new(expression_result))
+        let results = evaluated
+            .into_iter()
+            .map(|result| ExpressionResult {
+                outcome: Some(match result {
+                    Ok(var) => Outcome::Value(ValueResult {
+                        value: var.to_string(),
+                        column: Some(to_column(&var)),
+                    }),
+                    Err(message) => Outcome::Error(ErrorResult { message }),
+                }),
+            })
+            .collect();
+        Ok(Response::new(ExpressionResponse { results }))
+    }
+}
+
+fn to_var(column: Column) -> Result<Var, String> {
+    match column.values {
+        Some(Values::Ints(IntColumn { values })) => Ok(Var::from(values)),
+        Some(Values::Floats(FloatColumn { values })) => Ok(Var::from(values)),
+        Some(Values::Bools(BoolColumn { values })) => Ok(Var::from(values)),
+        None => Err("Binding has no values".to_owned()),
+    }
+}
+
+fn to_column(var: &Var) -> Column {
+    let values = match var {
+        Var::IntV(values) => Values::Ints(IntColumn {
+            values: values.to_vec(),
+        }),
+        Var::FloatV(values) => Values::Floats(FloatColumn {
+            values: values.to_vec(),
+        }),
+        Var::BoolV(_) | Var::PackedBoolV(_) => Values::Bools(BoolColumn {
+            values: var.bool().expect("Bool variants convert").into_owned(),
+        }),
+    };
+    Column {
+        values: Some(values),
     }
 }
 
@@ -29,9 +81,129 @@ impl ExecutorService for ExecutorRpcServer {
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let addr = "[::1]:50051".parse()?;
     let server = ExecutorRpcServer::default();
-    Server::builder()
-        .add_service(ExecutorServiceServer::new(server))
-        .serve(addr)
-        .await?;
+    let router = Server::builder().add_service(ExecutorServiceServer::new(server));
+    #[cfg(feature = "reflection")]
+    let router = router.add_service(reflection_service()?);
+    router.serve(addr).await?;
     Ok(())
 }
+
+// Check with `grpcurl -plaintext '[::1]:50051' list`, which should report `execserver.ExecutorService`.
+#[cfg(feature = "reflection")]
+fn reflection_service() -> Result<
+    tonic_reflection::server::v1::ServerReflectionServer<
+        impl tonic_reflection::server::v1::ServerReflection,
+    >,
+    tonic_reflection::server::Error,
+> {
+    tonic_reflection::server::Builder::configure()
+        .register_encoded_file_descriptor_set(execserver::FILE_DESCRIPTOR_SET)
+        .build_v1()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn batch_reports_each_outcome() {
+        let request = Request::new(ExpressionRequest {
+            expression: "1 + 2\n3 + * 4\n9223372036854775807 + 1".to_owned(),
+            ..Default::default()
+        });
+        let response = ExecutorRpcServer::default()
+            .execute_expression(request)
+            .await
+            .unwrap()
+            .into_inner();
+        let outcomes: Vec<_> = response
+            .results
+            .into_iter()
+            .map(|r| r.outcome.unwrap())
+            .collect();
+        assert!(matches!(
+            outcomes.as_slice(),
+            [
+                Outcome::Value(ValueResult { value, .. }),
+                Outcome::Error(_),
+                Outcome::Error(_),
+            ] if value == "[3]"
+        ));
+    }
+
+    #[tokio::test]
+    async fn evaluates_over_bound_column() {
+        let xs: Vec<i64> = (0..1000).collect();
+        let request = Request::new(ExpressionRequest {
+            expression: ":x * 2 + 1".to_owned(),
+            bindings: [(
+                "x".to_owned(),
+                Column {
+                    values: Some(Values::Ints(IntColumn { values: xs.clone() })),
+                },
+            )]
+            .into(),
+        });
+        let response = ExecutorRpcServer::default()
+            .execute_expression(request)
+            .await
+            .unwrap()
+            .into_inner();
+        let [ExpressionResult {
+            outcome: Some(Outcome::Value(ValueResult { column, .. })),
+        }] = response.results.as_slice()
+        else {
+            panic!("expected one value, got {:?}", response.results);
+        };
+        let Some(Column {
+            values: Some(Values::Ints(IntColumn { values })),
+        }) = column
+        else {
+            panic!("expected an Int column, got {column:?}");
+        };
+        assert_eq!(*values, xs.iter().map(|x| x * 2 + 1).collect::<Vec<_>>());
+    }
+
+    #[cfg(feature = "reflection")]
+    #[tokio::test]
+    async fn reflection_lists_executor_service() {
+        use tonic_reflection::pb::v1::{
+            server_reflection_client::ServerReflectionClient,
+            server_reflection_request::MessageRequest, server_reflection_response::MessageResponse,
+            ServerReflectionRequest,
+        };
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(
+            Server::builder()
+                .add_service(reflection_service().unwrap())
+                .serve_with_incoming(tokio_stream::wrappers::TcpListenerStream::new(listener)),
+        );
+
+        let channel = tonic::transport::Endpoint::from_shared(format!("http://{addr}"))
+            .unwrap()
+            .connect()
+            .await
+            .unwrap();
+        let mut client = ServerReflectionClient::new(channel);
+        let request = ServerReflectionRequest {
+            host: String::new(),
+            message_request: Some(MessageRequest::ListServices(String::new())),
+        };
+        let mut responses = client
+            .server_reflection_info(tokio_stream::once(request))
+            .await
+            .unwrap()
+            .into_inner();
+        let response = responses.message().await.unwrap().unwrap();
+        let Some(MessageResponse::ListServicesResponse(services)) = response.message_response
+        else {
+            panic!("Unexpected reflection response");
+        };
+        assert!(services
+            .service
+            .iter()
+            .any(|s| s.name == "execserver.ExecutorService"));
+    }
+}